@@ -0,0 +1,18 @@
+adc_u64:
+	//APP
+	cmp	w2, #1
+	adcs	x0, x0, x1
+	cset	w2, hs
+	//NO_APP
+	cmp	w2, #0
+	cset	w1, ne
+	ret
+sbb_u64:
+	//APP
+	cmp	wzr, w2
+	sbcs	x0, x0, x1
+	cset	w2, lo
+	//NO_APP
+	cmp	w2, #0
+	cset	w1, ne
+	ret
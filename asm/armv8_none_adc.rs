@@ -0,0 +1,12 @@
+extern crate bitintr;
+use bitintr::*;
+
+#[no_mangle]
+pub fn adc_u64(x: u64, y: u64, c: bool) -> (u64, bool) {
+    x.adc(y, c)
+}
+
+#[no_mangle]
+pub fn sbb_u64(x: u64, y: u64, b: bool) -> (u64, bool) {
+    x.sbb(y, b)
+}
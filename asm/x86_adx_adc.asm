@@ -0,0 +1,26 @@
+_adc_u64:
+	pushq	%rbp
+	movq	%rsp, %rbp
+	movq	%rdi, %rax
+	## InlineAsm Start
+	addb	$-1, %dl
+	adcxq	%rsi, %rax
+	setb	%dl
+	## InlineAsm End
+	testb	%dl, %dl
+	setne	%dl
+	popq	%rbp
+	retq
+_adox_u64:
+	pushq	%rbp
+	movq	%rsp, %rbp
+	movq	%rdi, %rax
+	## InlineAsm Start
+	addb	$127, %dl
+	adoxq	%rsi, %rax
+	seto	%dl
+	## InlineAsm End
+	testb	%dl, %dl
+	setne	%dl
+	popq	%rbp
+	retq
@@ -0,0 +1,12 @@
+extern crate bitintr;
+use bitintr::*;
+
+#[no_mangle]
+pub fn adc_u64(x: u64, y: u64, c: bool) -> (u64, bool) {
+    x.adc(y, c)
+}
+
+#[no_mangle]
+pub fn adox_u64(x: u64, y: u64, o: bool) -> (u64, bool) {
+    x.adox(y, o)
+}
@@ -0,0 +1,12 @@
+_rorx_u32:
+	pushq	%rbp
+	movq	%rsp, %rbp
+	rorxl	$7, %edi, %eax
+	popq	%rbp
+	retq
+_rorx_u64:
+	pushq	%rbp
+	movq	%rsp, %rbp
+	rorxq	$7, %rdi, %rax
+	popq	%rbp
+	retq
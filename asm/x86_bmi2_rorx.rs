@@ -0,0 +1,12 @@
+extern crate bitintr;
+use bitintr::*;
+
+#[no_mangle]
+pub fn rorx_u32(x: u32) -> u32 {
+    x.rorx(7)
+}
+
+#[no_mangle]
+pub fn rorx_u64(x: u64) -> u64 {
+    x.rorx(7)
+}
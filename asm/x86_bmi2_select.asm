@@ -0,0 +1,19 @@
+_select_u64:
+	pushq	%rbp
+	movq	%rsp, %rbp
+	cmpl	$63, %esi
+	jbe	LBB0_2
+	xorl	%eax, %eax
+	popq	%rbp
+	retq
+LBB0_2:
+	movl	$1, %eax
+	shlxq	%rsi, %rax, %rax
+	pdepq	%rdi, %rax, %rax
+	movl	$64, %edx
+	rep		bsfq	%rax, %rdx
+	xorl	%eax, %eax
+	cmpq	$64, %rdx
+	setb	%al
+	popq	%rbp
+	retq
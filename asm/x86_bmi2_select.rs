@@ -0,0 +1,7 @@
+extern crate bitintr;
+use bitintr::*;
+
+#[no_mangle]
+pub fn select_u64(x: u64, k: u32) -> Option<u32> {
+    x.select(k)
+}
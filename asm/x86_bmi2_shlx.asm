@@ -0,0 +1,18 @@
+_shlx_u32:
+	pushq	%rbp
+	movq	%rsp, %rbp
+	shlxl	%esi, %edi, %eax
+	popq	%rbp
+	retq
+_shrx_u64:
+	pushq	%rbp
+	movq	%rsp, %rbp
+	shrxq	%rsi, %rdi, %rax
+	popq	%rbp
+	retq
+_sarx_u64:
+	pushq	%rbp
+	movq	%rsp, %rbp
+	sarxq	%rsi, %rdi, %rax
+	popq	%rbp
+	retq
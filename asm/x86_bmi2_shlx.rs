@@ -0,0 +1,17 @@
+extern crate bitintr;
+use bitintr::*;
+
+#[no_mangle]
+pub fn shlx_u32(x: u32, n: u32) -> u32 {
+    x.shlx(n)
+}
+
+#[no_mangle]
+pub fn shrx_u64(x: u64, n: u32) -> u64 {
+    x.shrx(n)
+}
+
+#[no_mangle]
+pub fn sarx_u64(x: u64, n: u32) -> u64 {
+    x.sarx(n)
+}
@@ -0,0 +1,18 @@
+_adc_u64:
+	pushq	%rbp
+	movq	%rsp, %rbp
+	movq	%rdi, %rax
+	addb	$-1, %dl
+	adcq	%rsi, %rax
+	setb	%dl
+	popq	%rbp
+	retq
+_sbb_u64:
+	pushq	%rbp
+	movq	%rsp, %rbp
+	movq	%rdi, %rax
+	addb	$-1, %dl
+	sbbq	%rsi, %rax
+	setb	%dl
+	popq	%rbp
+	retq
@@ -0,0 +1,32 @@
+_fshl_u32:
+	pushq	%rbp
+	movq	%rsp, %rbp
+	movl	%edx, %ecx
+	movl	%edi, %eax
+	shldl	%cl, %esi, %eax
+	popq	%rbp
+	retq
+_fshl_u64:
+	pushq	%rbp
+	movq	%rsp, %rbp
+	movl	%edx, %ecx
+	movq	%rdi, %rax
+	shldq	%cl, %rsi, %rax
+	popq	%rbp
+	retq
+_fshr_u32:
+	pushq	%rbp
+	movq	%rsp, %rbp
+	movl	%edx, %ecx
+	movl	%esi, %eax
+	shrdl	%cl, %edi, %eax
+	popq	%rbp
+	retq
+_fshr_u64:
+	pushq	%rbp
+	movq	%rsp, %rbp
+	movl	%edx, %ecx
+	movq	%rsi, %rax
+	shrdq	%cl, %rdi, %rax
+	popq	%rbp
+	retq
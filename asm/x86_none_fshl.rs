@@ -0,0 +1,22 @@
+extern crate bitintr;
+use bitintr::*;
+
+#[no_mangle]
+pub fn fshl_u32(hi: u32, lo: u32, n: u32) -> u32 {
+    hi.fshl(lo, n)
+}
+
+#[no_mangle]
+pub fn fshl_u64(hi: u64, lo: u64, n: u32) -> u64 {
+    hi.fshl(lo, n)
+}
+
+#[no_mangle]
+pub fn fshr_u32(hi: u32, lo: u32, n: u32) -> u32 {
+    hi.fshr(lo, n)
+}
+
+#[no_mangle]
+pub fn fshr_u64(hi: u64, lo: u64, n: u32) -> u64 {
+    hi.fshr(lo, n)
+}
@@ -0,0 +1,20 @@
+_parity_u8:
+	pushq	%rbp
+	movq	%rsp, %rbp
+	testb	%dil, %dil
+	setnp	%al
+	popq	%rbp
+	retq
+_parity_u64:
+	pushq	%rbp
+	movq	%rsp, %rbp
+	movq	%rdi, %rcx
+	shrq	$32, %rcx
+	xorq	%rdi, %rcx
+	movq	%rcx, %rax
+	shrq	$16, %rax
+	xorl	%ecx, %eax
+	xorb	%ah, %al
+	setnp	%al
+	popq	%rbp
+	retq
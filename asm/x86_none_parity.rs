@@ -0,0 +1,12 @@
+extern crate bitintr;
+use bitintr::*;
+
+#[no_mangle]
+pub fn parity_u8(x: u8) -> bool {
+    x.parity()
+}
+
+#[no_mangle]
+pub fn parity_u64(x: u64) -> bool {
+    x.parity()
+}
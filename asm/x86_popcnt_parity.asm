@@ -0,0 +1,14 @@
+_parity_u8:
+	pushq	%rbp
+	movq	%rsp, %rbp
+	testb	%dil, %dil
+	setnp	%al
+	popq	%rbp
+	retq
+_parity_u64:
+	pushq	%rbp
+	movq	%rsp, %rbp
+	popcntq	%rdi, %rax
+	andl	$1, %eax
+	popq	%rbp
+	retq
@@ -0,0 +1,47 @@
+#[macro_use]
+extern crate bencher;
+use bencher::Bencher;
+
+extern crate bitintr;
+use crate::bitintr::bmat::bmm_xor64x64;
+
+fn matrix(mut x: u64) -> [u64; 64] {
+    let mut m = [0; 64];
+    for row in m.iter_mut() {
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        *row = x;
+    }
+    m
+}
+
+fn bmm_xor64x64_rows_impl(a: &[u64; 64], b: &[u64; 64]) -> [u64; 64] {
+    let mut c = [0; 64];
+    for (c, &a) in c.iter_mut().zip(a.iter()) {
+        for (k, &b) in b.iter().enumerate() {
+            *c ^= b & ((a >> k) & 1).wrapping_neg();
+        }
+    }
+    c
+}
+
+fn bmm_xor64x64_runner<F: Fn(&[u64; 64], &[u64; 64]) -> [u64; 64]>(
+    bench: &mut Bencher,
+    f: F,
+) {
+    let (a, b) =
+        (matrix(0x2545_F491_4F6C_DD1D), matrix(0x9E37_79B9_7F4A_7C15));
+    bench.iter(|| f(bencher::black_box(&a), bencher::black_box(&b)))
+}
+
+fn bmm_xor64x64_bitintr(bench: &mut Bencher) {
+    bmm_xor64x64_runner(bench, bmm_xor64x64)
+}
+
+fn bmm_xor64x64_rows(bench: &mut Bencher) {
+    bmm_xor64x64_runner(bench, bmm_xor64x64_rows_impl)
+}
+
+benchmark_group!(bmat, bmm_xor64x64_bitintr, bmm_xor64x64_rows);
+benchmark_main!(bmat);
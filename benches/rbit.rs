@@ -7,7 +7,7 @@ use crate::bitintr::Rbit;
 
 fn u8_runner<F: Fn(u8) -> u8>(bench: &mut Bencher, f: F) {
     bench.iter(|| {
-        for v in 0..=u8::max_value() {
+        for v in 0..=u8::MAX {
             bencher::black_box(f(bencher::black_box(v)));
         }
     })
@@ -34,15 +34,15 @@ fn rbit_u8_bitintr(bench: &mut Bencher) {
 }
 
 fn rbit_u8_3(bench: &mut Bencher) {
-    u8_runner(bench, |x| rbit_u8_3_impl(x))
+    u8_runner(bench, rbit_u8_3_impl)
 }
 
 fn rbit_u8_4(bench: &mut Bencher) {
-    u8_runner(bench, |x| rbit_u8_4_impl(x))
+    u8_runner(bench, rbit_u8_4_impl)
 }
 
 fn rbit_u8_7(bench: &mut Bencher) {
-    u8_runner(bench, |x| rbit_u8_7_impl(x))
+    u8_runner(bench, rbit_u8_7_impl)
 }
 
 benchmark_group!(rbit, rbit_u8_bitintr, rbit_u8_3, rbit_u8_4, rbit_u8_7);
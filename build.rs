@@ -9,14 +9,11 @@ fn nightly_rustc() -> bool {
         let version = str::from_utf8(&output.stdout).ok()?;
         Some(version.contains("nightly"))
     }
-    if let Some(true) = op() {
-        true
-    } else {
-        false
-    }
+    matches!(op(), Some(true))
 }
 
 fn main() {
+    println!("cargo:rustc-check-cfg=cfg(bitintr_nightly)");
     if nightly_rustc() {
         println!("cargo:rustc-cfg=bitintr_nightly");
     }
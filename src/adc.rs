@@ -0,0 +1,281 @@
+//! adc
+
+/// Unsigned add with carry.
+pub trait Adc: crate::marker::Sized {
+    /// Unsigned add with carry.
+    ///
+    /// Adds `self`, `y` and `carry_in`, returning a pair `(sum, carry_out)`
+    /// with the wrapped sum and whether the addition overflowed.
+    ///
+    /// Chaining the carry through a sequence of additions computes the sum
+    /// of multi-word integers.
+    ///
+    /// # Instructions
+    ///
+    /// - [`ADC`](http://www.felixcloutier.com/x86/ADC.html):
+    ///   - Description: Add with carry.
+    ///   - Architecture: x86.
+    ///   - Registers: 8/16/32/64 bit.
+    /// - [`ADCX`](http://www.felixcloutier.com/x86/ADCX.html):
+    ///   - Description: Unsigned integer addition with carry flag.
+    ///   - Architecture: x86.
+    ///   - Instruction set: ADX.
+    ///   - Registers: 32/64 bit.
+    /// - [`ADCS`](https://developer.arm.com/documentation/ddi0602/latest/Base-Instructions/ADCS--Add-with-carry--setting-flags-):
+    ///   - Description: Add with carry, setting flags.
+    ///   - Architecture: AArch64.
+    ///   - Registers: 32/64 bit.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bitintr::*;
+    /// assert_eq!(200_u8.adc(50, false), (250, false));
+    /// assert_eq!(200_u8.adc(55, true), (0, true));
+    /// assert_eq!(u64::MAX.adc(u64::MAX, true), (u64::MAX, true));
+    ///
+    /// // 128-bit addition with two 64-bit words:
+    /// let (a_lo, a_hi) = (u64::MAX, 1_u64);
+    /// let (b_lo, b_hi) = (1_u64, 2_u64);
+    /// let (lo, c) = a_lo.adc(b_lo, false);
+    /// let (hi, c) = a_hi.adc(b_hi, c);
+    /// assert_eq!((lo, hi, c), (0, 4, false));
+    /// ```
+    fn adc(self, y: Self, carry_in: bool) -> (Self, bool);
+
+    /// Unsigned add with overflow.
+    ///
+    /// Adds `self`, `y` and `overflow_in`, returning the same
+    /// `(sum, overflow_out)` pair as [`Adc::adc`], but propagating the carry
+    /// through the overflow flag instead of through the carry flag.
+    ///
+    /// Since `adc` and `adox` write disjoint flags, a chain of `adc` and a
+    /// chain of `adox` can be interleaved, e.g., to accumulate the low and
+    /// the high halves of the partial products of a multi-word
+    /// multiplication, without either chain clobbering the carry of the
+    /// other.
+    ///
+    /// # Instructions
+    ///
+    /// - [`ADOX`](http://www.felixcloutier.com/x86/ADOX.html):
+    ///   - Description: Unsigned integer addition with overflow flag.
+    ///   - Architecture: x86.
+    ///   - Instruction set: ADX.
+    ///   - Registers: 32/64 bit.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bitintr::*;
+    /// assert_eq!(200_u8.adox(50, false), (250, false));
+    /// assert_eq!(200_u8.adox(55, true), (0, true));
+    ///
+    /// // Two independent 128-bit additions with interleaved carry chains:
+    /// let (lo0, c) = u64::MAX.adc(1, false);
+    /// let (lo1, o) = 1_u64.adox(u64::MAX, false);
+    /// let (hi0, c) = 1_u64.adc(2, c);
+    /// let (hi1, o) = 3_u64.adox(4, o);
+    /// assert_eq!((lo0, hi0, c), (0, 4, false));
+    /// assert_eq!((lo1, hi1, o), (0, 8, false));
+    /// ```
+    fn adox(self, y: Self, overflow_in: bool) -> (Self, bool);
+}
+
+macro_rules! adc_impl {
+    ($ty:ty) => {
+        #[inline]
+        fn adc_(x: $ty, y: $ty, carry_in: bool) -> ($ty, bool) {
+            let (s, c0) = x.overflowing_add(y);
+            let (s, c1) = s.overflowing_add(carry_in as $ty);
+            (s, c0 | c1)
+        }
+    };
+    ($ty:ty, $adcs:literal) => {
+        #[inline]
+        fn adc_(x: $ty, y: $ty, carry_in: bool) -> ($ty, bool) {
+            let (mut s, mut c) = (x, carry_in as u32);
+            // `CMP` sets the carry flag iff `c >= 1`, and `CSET` reads it
+            // back after `ADCS`.
+            unsafe {
+                core::arch::asm!(
+                    "cmp {c:w}, #1",
+                    $adcs,
+                    "cset {c:w}, hs",
+                    s = inout(reg) s,
+                    y = in(reg) y,
+                    c = inout(reg) c,
+                    options(pure, nomem, nostack),
+                );
+            }
+            (s, c != 0)
+        }
+    };
+    ($ty:ty, $intr:ident, $adcx:literal, $adox:literal) => {
+        cfg_if! {
+            if #[cfg(target_feature = "adx")] {
+                #[inline]
+                #[target_feature(enable = "adx")]
+                unsafe fn adc_(x: $ty, y: $ty, carry_in: bool) -> ($ty, bool) {
+                    let (mut s, mut c) = (x, carry_in as u8);
+                    // Adding `0xff` to `c` sets the carry flag iff `c == 1`.
+                    core::arch::asm!(
+                        "add {c}, -1",
+                        $adcx,
+                        "setc {c}",
+                        s = inout(reg) s,
+                        y = in(reg) y,
+                        c = inout(reg_byte) c,
+                        options(pure, nomem, nostack),
+                    );
+                    (s, c != 0)
+                }
+            } else {
+                #[inline]
+                fn adc_(x: $ty, y: $ty, carry_in: bool) -> ($ty, bool) {
+                    let mut s = 0;
+                    #[allow(unused_unsafe)]
+                    let c = unsafe {
+                        crate::arch::$intr(carry_in as u8, x, y, &mut s)
+                    };
+                    (s, c != 0)
+                }
+            }
+        }
+    };
+}
+
+macro_rules! adox_impl {
+    ($ty:ty, $intr:ident, $adcx:literal, $adox:literal) => {
+        cfg_if! {
+            if #[cfg(target_feature = "adx")] {
+                #[inline]
+                #[target_feature(enable = "adx")]
+                unsafe fn adox_(
+                    x: $ty, y: $ty, overflow_in: bool,
+                ) -> ($ty, bool) {
+                    let (mut s, mut o) = (x, overflow_in as u8);
+                    // Adding `0x7f` to `o` sets the overflow flag iff
+                    // `o == 1`.
+                    core::arch::asm!(
+                        "add {o}, 0x7f",
+                        $adox,
+                        "seto {o}",
+                        s = inout(reg) s,
+                        y = in(reg) y,
+                        o = inout(reg_byte) o,
+                        options(pure, nomem, nostack),
+                    );
+                    (s, o != 0)
+                }
+            } else {
+                adox_impl!($ty);
+            }
+        }
+    };
+    ($ty:ty $(,$args:tt)*) => {
+        #[inline]
+        fn adox_(x: $ty, y: $ty, overflow_in: bool) -> ($ty, bool) {
+            x.adc(y, overflow_in)
+        }
+    };
+}
+
+macro_rules! impl_adc {
+    ($id:ident $(,$args:tt)*) => {
+        impl Adc for $id {
+            #[inline]
+            #[allow(unused_unsafe)]
+            fn adc(self, y: Self, carry_in: bool) -> (Self, bool) {
+                adc_impl!($id $(,$args)*);
+                // UNSAFETY: this is always safe, because
+                // the unsafe `#[target_feature]` function
+                // is only generated when the feature is
+                // statically-enabled at compile-time.
+                unsafe { adc_(self, y, carry_in) }
+            }
+            #[inline]
+            #[allow(unused_unsafe)]
+            fn adox(self, y: Self, overflow_in: bool) -> (Self, bool) {
+                adox_impl!($id $(,$args)*);
+                // UNSAFETY: this is always safe, because
+                // the unsafe `#[target_feature]` function
+                // is only generated when the feature is
+                // statically-enabled at compile-time.
+                unsafe { adox_(self, y, overflow_in) }
+            }
+        }
+    };
+}
+
+impl_all!(impl_adc: u8, u16);
+
+cfg_if! {
+    if #[cfg(any(target_arch = "x86", target_arch = "x86_64"))] {
+        impl_adc!(
+            u32, _addcarry_u32, "adcx {s:e}, {y:e}", "adox {s:e}, {y:e}"
+        );
+        cfg_if! {
+            if #[cfg(target_arch = "x86_64")] {
+                impl_adc!(
+                    u64, _addcarry_u64, "adcx {s}, {y}", "adox {s}, {y}"
+                );
+            } else {
+                impl_all!(impl_adc: u64);
+            }
+        }
+    } else if #[cfg(target_arch = "aarch64")] {
+        impl_adc!(u32, "adcs {s:w}, {s:w}, {y:w}");
+        impl_adc!(u64, "adcs {s:x}, {s:x}, {y:x}");
+    } else {
+        impl_all!(impl_adc: u32, u64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{xorshift64, SEED};
+
+    fn check(x: u64, y: u64, c: bool) {
+        let s = u128::from(x) + u128::from(y) + u128::from(c);
+        let r = (s as u64, s >> 64 != 0);
+        assert_eq!(x.adc(y, c), r, "{} {} {}", x, y, c);
+        assert_eq!(x.adox(y, c), r, "{} {} {}", x, y, c);
+        let (x, y) = (x as u32, y as u32);
+        let s = u64::from(x) + u64::from(y) + u64::from(c);
+        let r = (s as u32, s >> 32 != 0);
+        assert_eq!(x.adc(y, c), r, "{} {} {}", x, y, c);
+        assert_eq!(x.adox(y, c), r, "{} {} {}", x, y, c);
+        let (x, y) = (x as u16, y as u16);
+        let s = u32::from(x) + u32::from(y) + u32::from(c);
+        let r = (s as u16, s >> 16 != 0);
+        assert_eq!(x.adc(y, c), r, "{} {} {}", x, y, c);
+        assert_eq!(x.adox(y, c), r, "{} {} {}", x, y, c);
+    }
+
+    #[test]
+    fn adc() {
+        for x in 0..=u8::MAX {
+            for y in 0..=u8::MAX {
+                for &c in &[false, true] {
+                    let s = u16::from(x) + u16::from(y) + u16::from(c);
+                    let r = (s as u8, s >> 8 != 0);
+                    assert_eq!(x.adc(y, c), r);
+                    assert_eq!(x.adox(y, c), r);
+                }
+            }
+        }
+        let mut next = xorshift64(SEED);
+        for _ in 0..10_000 {
+            let (x, y) = (next(), next());
+            check(x, y, false);
+            check(x, y, true);
+        }
+        for &x in &[0, 1, u64::MAX - 1, u64::MAX] {
+            for &y in &[0, 1, u64::MAX - 1, u64::MAX] {
+                check(x, y, false);
+                check(x, y, true);
+            }
+        }
+    }
+}
@@ -0,0 +1,104 @@
+//! bgrp
+
+use crate::Pext;
+
+/// Group bits
+pub trait Bgrp {
+    /// Group bits.
+    ///
+    /// Gathers the bits of `self` selected by the `mask` into the contiguous
+    /// low order bit positions of the result, and the remaining bits of
+    /// `self` into the high order bit positions. The relative order of the
+    /// bits within each group is preserved.
+    ///
+    /// This is equivalent to `x.pext(mask) | (x.pext(!mask) <<
+    /// mask.count_ones())`.
+    ///
+    /// **Keywords**: Group bits, sheep-and-goats, centrifuge.
+    ///
+    /// # Instructions
+    ///
+    /// - [`BGRP`](https://developer.arm.com/documentation/ddi0602/latest/SVE-Instructions/BGRP--Group-bits-to-right-or-left-as-selected-by-bitmask-):
+    ///   - Description: Group bits to right or left as selected by bitmask.
+    ///   - Architecture: AArch64.
+    ///   - Instruction set: SVE2 (BITPERM).
+    ///   - Registers: 8/16/32/64 bit vector lanes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bitintr::*;
+    /// let n = 0b1011_1110_1001_0011u16;
+    ///
+    /// let m0 = 0b0110_0011_1000_0101u16;
+    /// let s0 = 0b1111_0010_1011_0101u16;
+    ///
+    /// assert_eq!(n.bgrp(m0), s0);
+    /// assert_eq!(n.bgrp(0), n);
+    /// assert_eq!(n.bgrp(!0), n);
+    /// ```
+    fn bgrp(self, mask: Self) -> Self;
+}
+
+macro_rules! impl_bgrp {
+    ($id:ident) => {
+        impl Bgrp for $id {
+            #[inline]
+            fn bgrp(self, mask: Self) -> Self {
+                let lo = self.pext(mask);
+                let hi = self.pext(!mask);
+                lo | hi.checked_shl(mask.count_ones()).unwrap_or(0)
+            }
+        }
+    };
+}
+
+impl_all!(impl_bgrp: u8, u16, u32, u64, i8, i16, i32, i64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{pext, xorshift64, SEED};
+
+    /// Selected bits at the low end, the rest at the high end.
+    fn model(x: u64, mask: u64, bits: u32) -> u64 {
+        let not_mask = !mask & (u64::MAX >> (64 - bits));
+        let hi = pext(x, not_mask);
+        pext(x, mask) | hi.checked_shl(mask.count_ones()).unwrap_or(0)
+    }
+
+    fn check(x: u64, mask: u64) {
+        let r = model(x, mask, 64);
+        assert_eq!(x.bgrp(mask), r, "{} {}", x, mask);
+        assert_eq!((x as i64).bgrp(mask as i64), r as i64);
+        let (x, mask) = (x as u32, mask as u32);
+        let r = model(x.into(), mask.into(), 32) as u32;
+        assert_eq!(x.bgrp(mask), r, "{} {}", x, mask);
+        assert_eq!((x as i32).bgrp(mask as i32), r as i32);
+        let (x, mask) = (x as u16, mask as u16);
+        let r = model(x.into(), mask.into(), 16) as u16;
+        assert_eq!(x.bgrp(mask), r, "{} {}", x, mask);
+        assert_eq!((x as i16).bgrp(mask as i16), r as i16);
+    }
+
+    #[test]
+    fn bgrp() {
+        for x in 0..=u8::MAX {
+            for mask in 0..=u8::MAX {
+                let r = model(x.into(), mask.into(), 8) as u8;
+                assert_eq!(x.bgrp(mask), r, "{} {}", x, mask);
+                assert_eq!((x as i8).bgrp(mask as i8), r as i8);
+            }
+        }
+        let mut next = xorshift64(SEED);
+        for _ in 0..10_000 {
+            let (x, mask) = (next(), next());
+            check(x, mask);
+            check(x, mask & next());
+            check(x, mask | next());
+        }
+        for &mask in &[0, 1, u64::MAX >> 1, u64::MAX] {
+            check(next(), mask);
+        }
+    }
+}
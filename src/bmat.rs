@@ -0,0 +1,401 @@
+//! Bit-matrix operations over GF(2).
+//!
+//! An 8x8 bit matrix is stored in a `u64` whose byte `i` is row `i` and
+//! whose bit `j` within that byte is column `j`. A 64x64 bit matrix is
+//! stored in a `[u64; 64]` whose element `i` is row `i` and whose bit `j` is
+//! column `j`.
+//!
+//! The `bmm_xor` products are computed over GF(2), that is, using AND for
+//! multiplication and XOR for addition. The `bmm_or` products use OR for
+//! addition instead, which computes the boolean matrix product.
+
+use crate::gf2p8affine::splat;
+use crate::{Blsr, Tzcnt};
+
+const LSB: u64 = 0x0101_0101_0101_0101;
+
+cfg_if! {
+    if #[cfg(all(target_arch = "x86_64", target_feature = "gfni"))] {
+        /// Matrix whose byte `i` is the unit vector `1 << i`.
+        const UNIT_ROWS: u64 = 0x8040_2010_0804_0201;
+
+        #[inline]
+        fn transpose8x8_(x: u64) -> u64 {
+            use crate::Gf2p8Affine;
+            UNIT_ROWS.gf2p8affine(x.swap_bytes(), 0)
+        }
+
+        #[inline]
+        fn bmm_xor8x8_(a: u64, b: u64) -> u64 {
+            use crate::Gf2p8Affine;
+            a.gf2p8affine(transpose8x8_(b).swap_bytes(), 0)
+        }
+    } else {
+        #[inline]
+        fn transpose8x8_(mut x: u64) -> u64 {
+            let mut t = (x ^ (x >> 7)) & 0x00AA_00AA_00AA_00AA;
+            x ^= t ^ (t << 7);
+            t = (x ^ (x >> 14)) & 0x0000_CCCC_0000_CCCC;
+            x ^= t ^ (t << 14);
+            t = (x ^ (x >> 28)) & 0x0000_0000_F0F0_F0F0;
+            x ^ t ^ (t << 28)
+        }
+
+        #[inline]
+        fn bmm_xor8x8_(a: u64, b: u64) -> u64 {
+            let mut c = 0;
+            for k in 0..8 {
+                c ^= (((a >> k) & LSB) * 0xFF) & splat((b >> (8 * k)) as u8);
+            }
+            c
+        }
+    }
+}
+
+cfg_if! {
+    if #[cfg(all(
+        target_arch = "x86_64",
+        target_feature = "gfni",
+        target_feature = "avx512f",
+        target_feature = "avx512vbmi"
+    ))] {
+        /// Byte indices that transpose the 8x8 byte matrix of a 512-bit
+        /// vector, whose qword `i` is row `i`.
+        static TRANSPOSE_BYTES: [u8; 64] = {
+            let mut idx = [0; 64];
+            let mut i = 0;
+            while i < 64 {
+                idx[i] = ((i % 8) * 8 + i / 8) as u8;
+                i += 1;
+            }
+            idx
+        };
+
+        /// Byte indices that transpose the 8x8 byte matrix of a 512-bit
+        /// vector, and then reverse the bytes of each qword.
+        static TRANSPOSE_SWAP_BYTES: [u8; 64] = {
+            let mut idx = [0; 64];
+            let mut i = 0;
+            while i < 64 {
+                idx[i] = ((7 - i % 8) * 8 + i / 8) as u8;
+                i += 1;
+            }
+            idx
+        };
+
+        /// Matrix whose byte `i` is the unit vector `1 << (7 - i)`.
+        const REVERSED_UNIT_ROWS: u64 = 0x0102_0408_1020_4080;
+
+        /// Multiplies the 8x8 blocks of the matrices with 512-bit
+        /// GF2P8AFFINEQB.
+        ///
+        /// Transposing the bytes of 8 rows gives a vector whose qword `k`
+        /// is the 8x8 block in columns `8 * k..8 * k + 8`. For each row
+        /// block `k` of `b`, the qwords `j` of `m[k]` are the affine
+        /// matrices of its blocks, see `bmm_xor8x8_`, so that the affine
+        /// transformation of the broadcast block `(i, k)` of `a` computes
+        /// the products of that block with all the blocks `(k, j)` of `b`.
+        #[inline]
+        #[target_feature(enable = "gfni,avx512f,avx512vbmi")]
+        unsafe fn bmm_xor64x64_(a: &[u64; 64], b: &[u64; 64]) -> [u64; 64] {
+            use crate::arch::*;
+            let t = _mm512_loadu_si512(TRANSPOSE_BYTES.as_ptr() as *const _);
+            let ts =
+                _mm512_loadu_si512(TRANSPOSE_SWAP_BYTES.as_ptr() as *const _);
+            let units = _mm512_set1_epi64(REVERSED_UNIT_ROWS as i64);
+            let mut m = [_mm512_setzero_si512(); 8];
+            for (m, rows) in m.iter_mut().zip(b.chunks(8)) {
+                let rows = _mm512_loadu_si512(rows.as_ptr() as *const _);
+                let blocks = _mm512_permutexvar_epi8(ts, rows);
+                *m = _mm512_gf2p8affine_epi64_epi8(units, blocks, 0);
+            }
+            let mut c = [0; 64];
+            for (c, a) in c.chunks_mut(8).zip(a.chunks(8)) {
+                let rows = _mm512_loadu_si512(a.as_ptr() as *const _);
+                let mut blocks = [0_u64; 8];
+                _mm512_storeu_si512(
+                    blocks.as_mut_ptr() as *mut _,
+                    _mm512_permutexvar_epi8(t, rows),
+                );
+                let mut acc = _mm512_setzero_si512();
+                for (&block, &m) in blocks.iter().zip(m.iter()) {
+                    let block = _mm512_set1_epi64(block as i64);
+                    acc = _mm512_xor_si512(
+                        acc,
+                        _mm512_gf2p8affine_epi64_epi8(block, m, 0),
+                    );
+                }
+                _mm512_storeu_si512(
+                    c.as_mut_ptr() as *mut _,
+                    _mm512_permutexvar_epi8(t, acc),
+                );
+            }
+            c
+        }
+    } else {
+        #[inline]
+        fn bmm_xor64x64_(a: &[u64; 64], b: &[u64; 64]) -> [u64; 64] {
+            let mut c = [0; 64];
+            for (c, &a) in c.iter_mut().zip(a.iter()) {
+                let mut a = a;
+                while a != 0 {
+                    *c ^= b[a.tzcnt() as usize];
+                    a = a.blsr();
+                }
+            }
+            c
+        }
+    }
+}
+
+/// Transposes an 8x8 bit matrix.
+///
+/// # Instructions
+///
+/// - [`GF2P8AFFINEQB`](http://www.felixcloutier.com/x86/GF2P8AFFINEQB.html):
+///   - Description: Galois Field affine transformation.
+///   - Architecture: x86.
+///   - Instruction set: GFNI.
+///   - Registers: 128/256/512 bit.
+///
+/// # Example
+///
+/// ```
+/// # use bitintr::bmat::*;
+/// // Row 0 is all ones, so column 0 of the transpose is all ones:
+/// assert_eq!(transpose8x8(0x0000_0000_0000_00FF), 0x0101_0101_0101_0101);
+/// assert_eq!(transpose8x8(0x8040_2010_0804_0201), 0x8040_2010_0804_0201);
+/// ```
+#[inline]
+pub fn transpose8x8(x: u64) -> u64 {
+    transpose8x8_(x)
+}
+
+/// Transposes a 64x64 bit matrix in place.
+///
+/// # Example
+///
+/// ```
+/// # use bitintr::bmat::*;
+/// let mut m = [0_u64; 64];
+/// m[0] = !0;
+/// transpose64x64(&mut m);
+/// assert!(m.iter().all(|&row| row == 1));
+/// ```
+#[inline]
+pub fn transpose64x64(m: &mut [u64; 64]) {
+    let mut j = 32;
+    let mut mask = 0x0000_0000_FFFF_FFFF_u64;
+    while j != 0 {
+        let mut k = 0;
+        while k < 64 {
+            // Swaps the high columns of row `k` with the low columns of row
+            // `k + j`.
+            let t = ((m[k] >> j) ^ m[k + j]) & mask;
+            m[k] ^= t << j;
+            m[k + j] ^= t;
+            k = (k + j + 1) & !j;
+        }
+        j >>= 1;
+        mask ^= mask << j;
+    }
+}
+
+/// Multiplies two 8x8 bit matrices over GF(2).
+///
+/// # Instructions
+///
+/// - [`GF2P8AFFINEQB`](http://www.felixcloutier.com/x86/GF2P8AFFINEQB.html):
+///   - Description: Galois Field affine transformation.
+///   - Architecture: x86.
+///   - Instruction set: GFNI.
+///   - Registers: 128/256/512 bit.
+///
+/// # Example
+///
+/// ```
+/// # use bitintr::bmat::*;
+/// const IDENTITY: u64 = 0x8040_2010_0804_0201;
+/// let a = 0x0123_4567_89AB_CDEF_u64;
+/// assert_eq!(bmm_xor8x8(a, IDENTITY), a);
+/// assert_eq!(bmm_xor8x8(IDENTITY, a), a);
+/// // Row 0 of `a` selects rows 0 and 1 of `b`, whose XOR is 0b11:
+/// assert_eq!(bmm_xor8x8(0b11, 0x02_01) & 0xFF, 0b11);
+/// ```
+#[inline]
+pub fn bmm_xor8x8(a: u64, b: u64) -> u64 {
+    bmm_xor8x8_(a, b)
+}
+
+/// Multiplies two 8x8 boolean bit matrices (AND/OR product).
+///
+/// # Example
+///
+/// ```
+/// # use bitintr::bmat::*;
+/// const IDENTITY: u64 = 0x8040_2010_0804_0201;
+/// let a = 0x0123_4567_89AB_CDEF_u64;
+/// assert_eq!(bmm_or8x8(a, IDENTITY), a);
+/// // Row 0 of `a` selects rows 0 and 1 of `b`, whose OR is 0b11:
+/// assert_eq!(bmm_or8x8(0b11, 0x03_01) & 0xFF, 0b11);
+/// ```
+#[inline]
+pub fn bmm_or8x8(a: u64, b: u64) -> u64 {
+    let mut c = 0;
+    for k in 0..8 {
+        c |= (((a >> k) & LSB) * 0xFF) & splat((b >> (8 * k)) as u8);
+    }
+    c
+}
+
+/// Multiplies two 64x64 bit matrices over GF(2).
+///
+/// # Instructions
+///
+/// - [`GF2P8AFFINEQB`](http://www.felixcloutier.com/x86/GF2P8AFFINEQB.html):
+///   - Description: Galois Field affine transformation, applied to the
+///     8x8 blocks of the matrices.
+///   - Architecture: x86.
+///   - Instruction set: GFNI, AVX-512F and AVX-512VBMI.
+///   - Registers: 512 bit.
+/// - [`VPERMB`](http://www.felixcloutier.com/x86/VPERMB.html):
+///   - Description: Permute packed bytes, which converts between rows and
+///     8x8 blocks.
+///   - Architecture: x86.
+///   - Instruction set: AVX-512VBMI.
+///   - Registers: 512 bit.
+/// - Otherwise, the rows of `b` selected by the bits set of each row of `a`
+///   are XORed together.
+///
+/// # Example
+///
+/// ```
+/// # use bitintr::bmat::*;
+/// let mut identity = [0_u64; 64];
+/// for (i, row) in identity.iter_mut().enumerate() {
+///     *row = 1 << i;
+/// }
+/// let mut a = [0_u64; 64];
+/// for (i, row) in a.iter_mut().enumerate() {
+///     *row = 0x0123_4567_89AB_CDEF_u64.rotate_left(i as u32);
+/// }
+/// assert_eq!(&bmm_xor64x64(&a, &identity)[..], &a[..]);
+/// assert_eq!(&bmm_xor64x64(&identity, &a)[..], &a[..]);
+/// ```
+#[inline]
+#[allow(unused_unsafe)]
+pub fn bmm_xor64x64(a: &[u64; 64], b: &[u64; 64]) -> [u64; 64] {
+    // UNSAFETY: this is always safe, because
+    // the unsafe `#[target_feature]` function
+    // is only generated when the feature is
+    // statically-enabled at compile-time.
+    unsafe { bmm_xor64x64_(a, b) }
+}
+
+/// Multiplies two 64x64 boolean bit matrices (AND/OR product).
+///
+/// # Example
+///
+/// ```
+/// # use bitintr::bmat::*;
+/// let mut a = [0_u64; 64];
+/// let mut b = [0_u64; 64];
+/// a[0] = 0b11;
+/// b[0] = 0b0110;
+/// b[1] = 0b1100;
+/// assert_eq!(bmm_or64x64(&a, &b)[0], 0b1110);
+/// ```
+#[inline]
+pub fn bmm_or64x64(a: &[u64; 64], b: &[u64; 64]) -> [u64; 64] {
+    let mut c = [0; 64];
+    for (c, &a) in c.iter_mut().zip(a.iter()) {
+        let mut a = a;
+        while a != 0 {
+            *c |= b[a.tzcnt() as usize];
+            a = a.blsr();
+        }
+    }
+    c
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::xorshift64;
+
+    fn get(m: &[u64], i: usize, j: usize) -> u64 {
+        (m[i] >> j) & 1
+    }
+
+    fn rows8x8(x: u64) -> [u64; 8] {
+        let mut r = [0; 8];
+        for (i, r) in r.iter_mut().enumerate() {
+            *r = (x >> (8 * i)) & 0xFF;
+        }
+        r
+    }
+
+    fn matrices() -> impl Iterator<Item = [u64; 64]> {
+        const SEEDS: &[u64] =
+            &[0x0123_4567_89AB_CDEF, 0xDEAD_BEEF_F00D_CAFE, !0];
+        SEEDS.iter().map(|&s| {
+            let mut m = [0; 64];
+            let mut next = xorshift64(s);
+            for row in m.iter_mut() {
+                *row = next();
+            }
+            m
+        })
+    }
+
+    #[test]
+    fn transpose() {
+        for m in matrices() {
+            let mut t = m;
+            transpose64x64(&mut t);
+            for i in 0..64 {
+                for j in 0..64 {
+                    assert_eq!(get(&t, i, j), get(&m, j, i));
+                }
+            }
+            let x = m[0];
+            let (r, t) = (rows8x8(x), rows8x8(transpose8x8(x)));
+            for i in 0..8 {
+                for j in 0..8 {
+                    assert_eq!(get(&t, i, j), get(&r, j, i));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn bmm() {
+        for (a, b) in matrices().zip(matrices().skip(1)) {
+            let (c_xor, c_or) = (bmm_xor64x64(&a, &b), bmm_or64x64(&a, &b));
+            for i in 0..64 {
+                for j in 0..64 {
+                    let ks = (0..64).map(|k| get(&a, i, k) & get(&b, k, j));
+                    assert_eq!(
+                        get(&c_xor, i, j),
+                        ks.clone().fold(0, |x, y| x ^ y)
+                    );
+                    assert_eq!(get(&c_or, i, j), ks.fold(0, |x, y| x | y));
+                }
+            }
+            let (a, b) = (a[0], b[0]);
+            let (ra, rb) = (rows8x8(a), rows8x8(b));
+            let (c_xor, c_or) =
+                (rows8x8(bmm_xor8x8(a, b)), rows8x8(bmm_or8x8(a, b)));
+            for i in 0..8 {
+                for j in 0..8 {
+                    let ks = (0..8).map(|k| get(&ra, i, k) & get(&rb, k, j));
+                    assert_eq!(
+                        get(&c_xor, i, j),
+                        ks.clone().fold(0, |x, y| x ^ y)
+                    );
+                    assert_eq!(get(&c_or, i, j), ks.fold(0, |x, y| x | y));
+                }
+            }
+        }
+    }
+}
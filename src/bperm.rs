@@ -0,0 +1,247 @@
+//! bperm
+
+/// Bit permute
+pub trait Bperm {
+    /// Bit permute.
+    ///
+    /// Gathers up to 8 arbitrary bits of `self` into the result. Byte `j` of
+    /// `indices` holds the index of the bit of `self` that is written to bit
+    /// `j` of the result. Indices greater than or equal to the bit size of
+    /// `self` select a zero bit.
+    ///
+    /// Unlike [`Pext`](trait.Pext.html), the gathered bits do not need to be
+    /// in increasing order, and the same bit can be gathered more than once.
+    ///
+    /// **Keywords**: Bit permute, bit gather, bit shuffle.
+    ///
+    /// # Instructions
+    ///
+    /// - [`VPSHUFBITQMB`](http://www.felixcloutier.com/x86/VPSHUFBITQMB.html):
+    ///   - Description: Shuffle bits from quadword elements using byte
+    ///     indexes into mask.
+    ///   - Architecture: x86.
+    ///   - Instruction set: AVX-512 BITALG.
+    ///   - Registers: 128/256/512 bit.
+    /// - [`BPERMD`](https://openpowerfoundation.org/specifications/isa/):
+    ///   - Description: Bit permute doubleword.
+    ///   - Architecture: PowerPC.
+    ///   - Instruction set: Power ISA 2.06.
+    ///   - Registers: 64 bit.
+    /// - Note: `BPERMD` numbers the bits and the bytes from the most
+    ///   significant end.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bitintr::*;
+    /// let n = 0b1010_0110u8;
+    /// // Gathers bits 1, 2, 5, 7, 7, 0, 8 and 3 of `n`:
+    /// let i = 0x03_08_00_07_07_05_02_01u64;
+    ///
+    /// assert_eq!(n.bperm(i), 0b0001_1111u8);
+    /// assert_eq!(n.bperm(0x07_06_05_04_03_02_01_00), n);
+    /// ```
+    fn bperm(self, indices: u64) -> u8;
+
+    /// Bit permute of 64 bits.
+    ///
+    /// Gathers up to 64 arbitrary bits of `self` into the result. Bit `j` of
+    /// the result is the bit of `self` at index `indices[j]`. Indices greater
+    /// than or equal to the bit size of `self` select a zero bit.
+    ///
+    /// See [`bperm`](#tymethod.bperm).
+    ///
+    /// # Instructions
+    ///
+    /// - [`VPSHUFBITQMB`](http://www.felixcloutier.com/x86/VPSHUFBITQMB.html):
+    ///   - Description: Shuffle bits from quadword elements using byte
+    ///     indexes into mask.
+    ///   - Architecture: x86.
+    ///   - Instruction set: AVX-512 BITALG.
+    ///   - Registers: 512 bit.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bitintr::*;
+    /// let n = 0x8000_0000_0000_0001u64;
+    ///
+    /// let mut i = [0u8; 64];
+    /// for (j, i) in i.iter_mut().enumerate() {
+    ///     *i = 63 - j as u8;
+    /// }
+    ///
+    /// assert_eq!(n.bperm64(&i), n.rbit());
+    /// assert_eq!(0xFFu8.bperm64(&i), 0xFF00_0000_0000_0000u64);
+    /// ```
+    fn bperm64(self, indices: &[u8; 64]) -> u64;
+}
+
+macro_rules! bperm_impl {
+    ($ty:ty) => {
+        cfg_if! {
+            if #[cfg(all(
+                any(target_arch = "x86", target_arch = "x86_64"),
+                target_feature = "avx512bitalg"
+            ))] {
+                #[inline]
+                #[target_feature(enable = "avx512bitalg")]
+                unsafe fn bperm_(value: u64, indices: u64) -> u8 {
+                    use crate::arch::*;
+                    const BIT_WIDTH: i8 =
+                        (crate::mem::size_of::<$ty>() * 8) as i8;
+                    let b = _mm512_set1_epi64(value as i64);
+                    let c =
+                        _mm512_set_epi64(0, 0, 0, 0, 0, 0, 0, indices as i64);
+                    let in_range =
+                        _mm512_cmplt_epu8_mask(c, _mm512_set1_epi8(BIT_WIDTH));
+                    (_mm512_bitshuffle_epi64_mask(b, c) & in_range) as u8
+                }
+            } else {
+                #[inline]
+                fn bperm_(value: u64, indices: u64) -> u8 {
+                    const BIT_WIDTH: u64 =
+                        (crate::mem::size_of::<$ty>() * 8) as u64;
+                    let mut r = 0;
+                    for j in 0..8 {
+                        let i = (indices >> (8 * j)) & 0xFF;
+                        if i < BIT_WIDTH {
+                            r |= (((value >> i) & 1) as u8) << j;
+                        }
+                    }
+                    r
+                }
+            }
+        }
+    };
+}
+
+macro_rules! bperm64_impl {
+    ($ty:ty) => {
+        cfg_if! {
+            if #[cfg(all(
+                any(target_arch = "x86", target_arch = "x86_64"),
+                target_feature = "avx512bitalg"
+            ))] {
+                #[inline]
+                #[target_feature(enable = "avx512bitalg")]
+                unsafe fn bperm64_(value: u64, indices: &[u8; 64]) -> u64 {
+                    use crate::arch::*;
+                    const BIT_WIDTH: i8 =
+                        (crate::mem::size_of::<$ty>() * 8) as i8;
+                    let b = _mm512_set1_epi64(value as i64);
+                    let c = _mm512_loadu_si512(indices.as_ptr() as *const _);
+                    let in_range =
+                        _mm512_cmplt_epu8_mask(c, _mm512_set1_epi8(BIT_WIDTH));
+                    _mm512_bitshuffle_epi64_mask(b, c) & in_range
+                }
+            } else {
+                #[inline]
+                fn bperm64_(value: u64, indices: &[u8; 64]) -> u64 {
+                    const BIT_WIDTH: u8 =
+                        (crate::mem::size_of::<$ty>() * 8) as u8;
+                    let mut r = 0;
+                    for (j, &i) in indices.iter().enumerate() {
+                        if i < BIT_WIDTH {
+                            r |= ((value >> i) & 1) << j;
+                        }
+                    }
+                    r
+                }
+            }
+        }
+    };
+}
+
+macro_rules! impl_bperm {
+    ($id:ident, $uid:ident) => {
+        impl Bperm for $id {
+            #[inline]
+            #[allow(unused_unsafe)]
+            fn bperm(self, indices: u64) -> u8 {
+                bperm_impl!($id);
+                // UNSAFETY: this is always safe, because
+                // the unsafe `#[target_feature]` function
+                // is only generated when the feature is
+                // statically-enabled at compile-time.
+                unsafe { bperm_(self as $uid as u64, indices) }
+            }
+
+            #[inline]
+            #[allow(unused_unsafe)]
+            fn bperm64(self, indices: &[u8; 64]) -> u64 {
+                bperm64_impl!($id);
+                // UNSAFETY: see `bperm`.
+                unsafe { bperm64_(self as $uid as u64, indices) }
+            }
+        }
+    };
+}
+
+impl_bperm!(u8, u8);
+impl_bperm!(i8, u8);
+impl_bperm!(u16, u16);
+impl_bperm!(i16, u16);
+impl_bperm!(u32, u32);
+impl_bperm!(i32, u32);
+impl_bperm!(u64, u64);
+impl_bperm!(i64, u64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{pext, xorshift64, SEED};
+
+    /// Indices of the bits set of `mask`, in increasing order, followed by
+    /// out of range indices.
+    fn indices(mask: u64) -> [u8; 64] {
+        let mut i = [0xFF; 64];
+        for (i, b) in
+            i.iter_mut().zip((0..64).filter(|b| mask & (1 << b) != 0))
+        {
+            *i = b;
+        }
+        i
+    }
+
+    macro_rules! check {
+        ($x:expr, $m:expr, $id:ident, $uid:ident) => {{
+            // Gathering the bits of a mask in increasing order is `pext`:
+            let (x, m) = ($x as $uid, $m as $uid);
+            let expected = pext(u64::from(x), u64::from(m));
+            let i = indices(u64::from(m));
+            assert_eq!(x.bperm64(&i), expected, "{:#x} {:#x}", x, m);
+            assert_eq!((x as $id).bperm64(&i), expected);
+            // `bperm` gathers the 8 lowest bits of the mask:
+            let m = i[..8]
+                .iter()
+                .filter(|&&b| b < 64)
+                .fold(0, |m, &b| m | 1 << b);
+            let mut i8 = [0; 8];
+            i8.copy_from_slice(&i[..8]);
+            let i = u64::from_le_bytes(i8);
+            let expected = pext(u64::from(x), m) as u8;
+            assert_eq!(x.bperm(i), expected, "{:#x} {:#x}", x, m);
+            assert_eq!((x as $id).bperm(i), expected);
+        }};
+    }
+
+    #[test]
+    fn bperm() {
+        for x in 0..=u8::MAX {
+            for m in 0..=u8::MAX {
+                check!(x, m, i8, u8);
+            }
+        }
+        let mut next = xorshift64(SEED);
+        for _ in 0..10_000 {
+            let (x, m) = (next(), next());
+            // Also sparse values and masks:
+            for &(x, m) in &[(x, m), (x & next() & next(), m & next())] {
+                check!(x, m, i16, u16);
+                check!(x, m, i32, u32);
+                check!(x, m, i64, u64);
+            }
+        }
+    }
+}
@@ -0,0 +1,111 @@
+//! cfuged
+
+/// Centrifuge
+pub trait Cfuged {
+    /// Centrifuge.
+    ///
+    /// Gathers the bits of `self` selected by the `mask` into the low order
+    /// bit positions of the result, and the remaining bits of `self` into the
+    /// high order bit positions, preserving their relative order.
+    ///
+    /// This is the same operation as [`Bgrp`](trait.Bgrp.html).
+    ///
+    /// **Keywords**: Centrifuge, sheep-and-goats, group bits.
+    ///
+    /// # Instructions
+    ///
+    /// - [`CFUGED`](https://openpowerfoundation.org/specifications/isa/):
+    ///   - Description: Centrifuge doubleword.
+    ///   - Architecture: PowerPC.
+    ///   - Instruction set: Power ISA 3.1.
+    ///   - Registers: 64 bit.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bitintr::*;
+    /// let n = 0b1011_1110_1001_0011u16;
+    /// let m = 0b0110_0011_1000_0101u16;
+    ///
+    /// assert_eq!(n.cfuged(m), 0b1111_0010_1011_0101u16);
+    /// assert_eq!(n.cfuged(m), n.bgrp(m));
+    /// ```
+    fn cfuged(self, mask: Self) -> Self;
+}
+
+macro_rules! cfuged_impl {
+    ($ty:ty) => {
+        cfg_if! {
+            if #[cfg(all(
+                target_arch = "powerpc64",
+                target_feature = "power10-vector",
+                bitintr_nightly
+            ))] {
+                #[inline]
+                fn cfuged_(value: $ty, mask: $ty) -> $ty {
+                    power10_asm!("cfuged", $ty, value, mask)
+                }
+            } else {
+                #[inline]
+                fn cfuged_(value: $ty, mask: $ty) -> $ty {
+                    crate::Bgrp::bgrp(value, mask)
+                }
+            }
+        }
+    };
+}
+
+macro_rules! impl_cfuged {
+    ($id:ident) => {
+        impl Cfuged for $id {
+            #[inline]
+            fn cfuged(self, mask: Self) -> Self {
+                cfuged_impl!($id);
+                cfuged_(self, mask)
+            }
+        }
+    };
+}
+
+impl_all!(impl_cfuged: u8, u16, u32, u64, i8, i16, i32, i64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{pext, xorshift64, SEED};
+
+    /// `pext(x, !mask)` above `pext(x, mask)`, within the bits of `width`.
+    fn cfuged_model(x: u64, mask: u64, width: u64) -> u64 {
+        let hi = pext(x, !mask & width);
+        hi.checked_shl(mask.count_ones()).unwrap_or(0) | pext(x, mask)
+    }
+
+    macro_rules! check {
+        ($x:expr, $m:expr, $id:ident, $uid:ident) => {{
+            let (x, m) = ($x as $uid, $m as $uid);
+            let width = u64::from($uid::MAX);
+            let expected = cfuged_model(u64::from(x), u64::from(m), width);
+            assert_eq!(u64::from(x.cfuged(m)), expected, "{:#x} {:#x}", x, m);
+            assert_eq!((x as $id).cfuged(m as $id), expected as $id);
+        }};
+    }
+
+    #[test]
+    fn cfuged() {
+        for x in 0..=u8::MAX {
+            for m in 0..=u8::MAX {
+                check!(x, m, i8, u8);
+            }
+        }
+        let mut next = xorshift64(SEED);
+        for _ in 0..10_000 {
+            let (x, m) = (next(), next());
+            // Also sparse values and masks:
+            for &(x, m) in &[(x, m), (x & next() & next(), m & next())] {
+                check!(x, m, i16, u16);
+                check!(x, m, i32, u32);
+                check!(x, m, i64, u64);
+            }
+        }
+    }
+}
@@ -0,0 +1,119 @@
+//! clmul
+
+/// Carry-less multiply.
+pub trait Clmul: crate::marker::Sized {
+    /// Carry-less multiply.
+    ///
+    /// Multiplies `self` with `y` as polynomials over GF(2), that is, like
+    /// the long multiplication of [`Mulx`](trait.Mulx.html) but adding the
+    /// partial products with XOR instead of with carries. Returns a pair
+    /// `(lo, hi)` with the low half and the high half of the result.
+    ///
+    /// # Instructions
+    ///
+    /// - [`PCLMULQDQ`](http://www.felixcloutier.com/x86/PCLMULQDQ.html):
+    ///   - Description: Carry-less multiplication quadword.
+    ///   - Architecture: x86.
+    ///   - Instruction set: PCLMULQDQ.
+    ///   - Registers: 128 bit.
+    /// - [`PMULL`](http://infocenter.arm.com/help/index.jsp?topic=/com.arm.
+    ///   doc.ddi0487a.k_10775/index.html):
+    ///   - Description: Polynomial multiply long.
+    ///   - Architecture: ARMv8.
+    ///   - Instruction set: AES.
+    ///   - Registers: 64 bit.
+    /// - [`CLMUL`, `CLMULH`](https://github.com/riscv/riscv-bitmanip):
+    ///   - Description: Carry-less multiply (low / high part).
+    ///   - Architecture: RISC-V.
+    ///   - Instruction set: Zbc.
+    ///   - Registers: 32/64 bit.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bitintr::*;
+    /// // (x + 1) * (x + 1) == x^2 + 1
+    /// assert_eq!(0b11_u8.clmul(0b11), (0b101, 0));
+    /// assert_eq!(0xFF_u8.clmul(0xFF), (0b0101_0101, 0b0101_0101));
+    /// assert_eq!((1_u64 << 63).clmul(1 << 63), (0, 1 << 62));
+    /// ```
+    fn clmul(self, y: Self) -> (Self, Self);
+}
+
+macro_rules! clmul_impl {
+    ($ty:ty, $wide:ty) => {
+        #[inline]
+        fn clmul_(x: $ty, y: $ty) -> ($ty, $ty) {
+            const BIT_WIDTH: u32 = (crate::mem::size_of::<$ty>() * 8) as u32;
+            let x = <$wide>::from(x);
+            let mut r: $wide = 0;
+            for i in 0..BIT_WIDTH {
+                let mask = <$wide>::from((y >> i) & 1).wrapping_neg();
+                r ^= (x << i) & mask;
+            }
+            (r as $ty, (r >> BIT_WIDTH) as $ty)
+        }
+    };
+    (u64) => {
+        cfg_if! {
+            if #[cfg(all(
+                target_arch = "x86_64",
+                target_feature = "pclmulqdq"
+            ))] {
+                #[inline]
+                #[target_feature(enable = "pclmulqdq")]
+                unsafe fn clmul_(x: u64, y: u64) -> (u64, u64) {
+                    use crate::arch::*;
+                    let r = _mm_clmulepi64_si128(
+                        _mm_cvtsi64_si128(x as i64),
+                        _mm_cvtsi64_si128(y as i64),
+                        0,
+                    );
+                    (
+                        _mm_cvtsi128_si64(r) as u64,
+                        _mm_cvtsi128_si64(_mm_unpackhi_epi64(r, r)) as u64,
+                    )
+                }
+            } else if #[cfg(all(
+                target_arch = "aarch64",
+                target_feature = "aes"
+            ))] {
+                #[inline]
+                #[target_feature(enable = "neon,aes")]
+                unsafe fn clmul_(x: u64, y: u64) -> (u64, u64) {
+                    let r = crate::arch::vmull_p64(x, y);
+                    (r as u64, (r >> 64) as u64)
+                }
+            } else {
+                clmul_impl!(u64, u128);
+            }
+        }
+    };
+}
+
+macro_rules! impl_clmul {
+    ($id:ident, $uid:ident $(,$args:tt)*) => {
+        impl Clmul for $id {
+            #[inline]
+            #[allow(unused_unsafe)]
+            fn clmul(self, y: Self) -> (Self, Self) {
+                clmul_impl!($uid $(,$args)*);
+                // UNSAFETY: this is always safe, because
+                // the unsafe `#[target_feature]` function
+                // is only generated when the feature is
+                // statically-enabled at compile-time.
+                let (lo, hi) = unsafe { clmul_(self as $uid, y as $uid) };
+                (lo as Self, hi as Self)
+            }
+        }
+    };
+}
+
+impl_clmul!(u8, u8, u16);
+impl_clmul!(u16, u16, u32);
+impl_clmul!(u32, u32, u64);
+impl_clmul!(u64, u64);
+impl_clmul!(i8, u8, u16);
+impl_clmul!(i16, u16, u32);
+impl_clmul!(i32, u32, u64);
+impl_clmul!(i64, u64);
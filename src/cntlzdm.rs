@@ -0,0 +1,117 @@
+//! cntlzdm
+
+/// Count leading zeros under mask
+pub trait Cntlzdm {
+    /// Counts the leading zeros of `self` under a bit `mask`.
+    ///
+    /// Only the bits of `self` selected by the `mask` are considered,
+    /// starting from the most significant one. When all of them are zero,
+    /// it returns the number of bits set in the `mask`.
+    ///
+    /// This is equivalent to counting the leading zeros of `x.pext(mask)`
+    /// restricted to its low `mask.count_ones()` bits.
+    ///
+    /// **Keywords**: Count leading zeros under mask, masked count leading
+    /// zeros.
+    ///
+    /// # Instructions
+    ///
+    /// - [`CNTLZDM`](https://openpowerfoundation.org/specifications/isa/):
+    ///   - Description: Count leading zeros doubleword under bit mask.
+    ///   - Architecture: PowerPC.
+    ///   - Instruction set: Power ISA 3.1.
+    ///   - Registers: 64 bit.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bitintr::*;
+    /// let n = 0b0000_1010_1111_0000u16;
+    /// let m = 0b1111_0101_0011_0000u16;
+    /// // The 8 bits selected by the mask are `n.pext(m) == 0b0000_0011`.
+    /// assert_eq!(n.cntlzdm(m), 6);
+    /// assert_eq!(n.cntlzdm(0), 0);
+    /// assert_eq!(n.cntlzdm(!0), n.lzcnt());
+    /// ```
+    fn cntlzdm(self, mask: Self) -> Self;
+}
+
+macro_rules! cntlzdm_impl {
+    ($ty:ty) => {
+        cfg_if! {
+            if #[cfg(all(
+                target_arch = "powerpc64",
+                target_feature = "power10-vector",
+                bitintr_nightly
+            ))] {
+                #[inline]
+                fn cntlzdm_(value: $ty, mask: $ty) -> $ty {
+                    power10_asm!("cntlzdm", $ty, value, mask)
+                }
+            } else {
+                #[inline]
+                fn cntlzdm_(value: $ty, mask: $ty) -> $ty {
+                    const BIT_WIDTH: $ty =
+                        (crate::mem::size_of::<$ty>() * 8) as $ty;
+                    let n = mask.count_ones() as $ty;
+                    crate::Lzcnt::lzcnt(crate::Pext::pext(value, mask))
+                        - (BIT_WIDTH - n)
+                }
+            }
+        }
+    };
+}
+
+macro_rules! impl_cntlzdm {
+    ($id:ident) => {
+        impl Cntlzdm for $id {
+            #[inline]
+            fn cntlzdm(self, mask: Self) -> Self {
+                cntlzdm_impl!($id);
+                cntlzdm_(self, mask)
+            }
+        }
+    };
+}
+
+impl_all!(impl_cntlzdm: u8, u16, u32, u64, i8, i16, i32, i64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{pext, xorshift64, SEED};
+
+    /// Leading zeros of `pext(x, mask)` within its `popcnt(mask)` low bits.
+    fn cntlzdm_model(x: u64, mask: u64) -> u64 {
+        let (p, n) = (pext(x, mask), mask.count_ones());
+        (0..n).rev().take_while(|&i| (p >> i) & 1 == 0).count() as u64
+    }
+
+    macro_rules! check {
+        ($x:expr, $m:expr, $id:ident, $uid:ident) => {{
+            let (x, m) = ($x as $uid, $m as $uid);
+            let expected = cntlzdm_model(u64::from(x), u64::from(m));
+            assert_eq!(x.cntlzdm(m) as u64, expected, "{:#x} {:#x}", x, m);
+            assert_eq!((x as $id).cntlzdm(m as $id) as u64, expected);
+        }};
+    }
+
+    #[test]
+    fn cntlzdm() {
+        for x in 0..=u8::MAX {
+            for m in 0..=u8::MAX {
+                check!(x, m, i8, u8);
+            }
+        }
+        let mut next = xorshift64(SEED);
+        for _ in 0..10_000 {
+            let (x, m) = (next(), next());
+            // Also sparse values and masks:
+            for &(x, m) in &[(x, m), (x & next() & next(), m & next())] {
+                check!(x, m, i16, u16);
+                check!(x, m, i32, u32);
+                check!(x, m, i64, u64);
+            }
+        }
+    }
+}
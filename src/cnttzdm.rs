@@ -0,0 +1,114 @@
+//! cnttzdm
+
+/// Count trailing zeros under mask
+pub trait Cnttzdm {
+    /// Counts the trailing zeros of `self` under a bit `mask`.
+    ///
+    /// Only the bits of `self` selected by the `mask` are considered,
+    /// starting from the least significant one. When all of them are zero,
+    /// it returns the number of bits set in the `mask`.
+    ///
+    /// This is equivalent to counting the trailing zeros of `x.pext(mask)`
+    /// restricted to its low `mask.count_ones()` bits.
+    ///
+    /// **Keywords**: Count trailing zeros under mask, masked count trailing
+    /// zeros.
+    ///
+    /// # Instructions
+    ///
+    /// - [`CNTTZDM`](https://openpowerfoundation.org/specifications/isa/):
+    ///   - Description: Count trailing zeros doubleword under bit mask.
+    ///   - Architecture: PowerPC.
+    ///   - Instruction set: Power ISA 3.1.
+    ///   - Registers: 64 bit.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bitintr::*;
+    /// let n = 0b0000_1010_1111_0000u16;
+    /// let m = 0b0000_1101_0000_1111u16;
+    /// // The 7 bits selected by the mask are `n.pext(m) == 0b100_0000`.
+    /// assert_eq!(n.cnttzdm(m), 6);
+    /// assert_eq!(n.cnttzdm(0), 0);
+    /// assert_eq!(n.cnttzdm(!0), n.tzcnt());
+    /// ```
+    fn cnttzdm(self, mask: Self) -> Self;
+}
+
+macro_rules! cnttzdm_impl {
+    ($ty:ty) => {
+        cfg_if! {
+            if #[cfg(all(
+                target_arch = "powerpc64",
+                target_feature = "power10-vector",
+                bitintr_nightly
+            ))] {
+                #[inline]
+                fn cnttzdm_(value: $ty, mask: $ty) -> $ty {
+                    power10_asm!("cnttzdm", $ty, value, mask)
+                }
+            } else {
+                #[inline]
+                fn cnttzdm_(value: $ty, mask: $ty) -> $ty {
+                    let n = mask.count_ones() as $ty;
+                    crate::Tzcnt::tzcnt(crate::Pext::pext(value, mask)).min(n)
+                }
+            }
+        }
+    };
+}
+
+macro_rules! impl_cnttzdm {
+    ($id:ident) => {
+        impl Cnttzdm for $id {
+            #[inline]
+            fn cnttzdm(self, mask: Self) -> Self {
+                cnttzdm_impl!($id);
+                cnttzdm_(self, mask)
+            }
+        }
+    };
+}
+
+impl_all!(impl_cnttzdm: u8, u16, u32, u64, i8, i16, i32, i64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{pext, xorshift64, SEED};
+
+    /// Trailing zeros of `pext(x, mask)` within its `popcnt(mask)` low bits.
+    fn cnttzdm_model(x: u64, mask: u64) -> u64 {
+        let (p, n) = (pext(x, mask), mask.count_ones());
+        (0..n).take_while(|&i| (p >> i) & 1 == 0).count() as u64
+    }
+
+    macro_rules! check {
+        ($x:expr, $m:expr, $id:ident, $uid:ident) => {{
+            let (x, m) = ($x as $uid, $m as $uid);
+            let expected = cnttzdm_model(u64::from(x), u64::from(m));
+            assert_eq!(x.cnttzdm(m) as u64, expected, "{:#x} {:#x}", x, m);
+            assert_eq!((x as $id).cnttzdm(m as $id) as u64, expected);
+        }};
+    }
+
+    #[test]
+    fn cnttzdm() {
+        for x in 0..=u8::MAX {
+            for m in 0..=u8::MAX {
+                check!(x, m, i8, u8);
+            }
+        }
+        let mut next = xorshift64(SEED);
+        for _ in 0..10_000 {
+            let (x, m) = (next(), next());
+            // Also sparse values and masks:
+            for &(x, m) in &[(x, m), (x & next() & next(), m & next())] {
+                check!(x, m, i16, u16);
+                check!(x, m, i32, u32);
+                check!(x, m, i64, u64);
+            }
+        }
+    }
+}
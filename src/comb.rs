@@ -0,0 +1,393 @@
+//! Combinations of bits.
+//!
+//! A `k`-combination of `n` bits is an `n`-bit word with exactly `k` bits
+//! set. The combinations are ordered as integers, which is the
+//! lexicographic order of their bits from the most significant to the least
+//! significant one.
+//!
+//! The successor of a combination is computed with Gosper's hack, and the
+//! rank of a combination, its position in that order, with the
+//! combinatorial number system: the rank of the combination whose `j`-th
+//! lowest set bit is at position `c_j` is the sum of `C(c_j, j)` for `j` in
+//! `1..=k`.
+//!
+//! The functions of this module are implemented for `u8`, `u16`, `u32` and
+//! `u64`.
+//!
+//! # Example
+//!
+//! ```
+//! # use bitintr::comb::{self, Combinations};
+//! let all = Combinations::<u8>::new(4, 2).collect::<Vec<_>>();
+//! assert_eq!(all, [0b0011, 0b0101, 0b0110, 0b1001, 0b1010, 0b1100]);
+//!
+//! assert_eq!(comb::next_combination(0b0110_u8), Some(0b1001));
+//! assert_eq!(comb::prev_combination(0b1001_u8), Some(0b0110));
+//! assert_eq!(comb::rank(0b1001_u8), 3);
+//! assert_eq!(comb::unrank::<u8>(2, 3), Some(0b1001));
+//! ```
+
+use crate::word::Narrow;
+use crate::{Blsi, Popcnt, Tzcnt};
+use core::convert::TryFrom;
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+
+/// Mask of the `n` low bits, for `n` in `0..=64`.
+#[inline]
+fn low_bits(n: u32) -> u64 {
+    if n == 64 {
+        u64::MAX
+    } else {
+        (1 << n) - 1
+    }
+}
+
+/// Binomial coefficients: `BINOMIAL[n][k] == C(n, k)`.
+///
+/// All of them fit in a `u64`, the largest one is `C(64, 32) < 2^61`.
+static BINOMIAL: [[u64; 65]; 65] = {
+    let mut c = [[0; 65]; 65];
+    let mut n = 0;
+    while n <= 64 {
+        c[n][0] = 1;
+        let mut k = 1;
+        while k <= n {
+            c[n][k] = c[n - 1][k - 1] + c[n - 1][k];
+            k += 1;
+        }
+        n += 1;
+    }
+    c
+};
+
+/// Successor of `x` among the words of `width` bits with `popcnt(x)` bits
+/// set.
+#[inline]
+fn next_(x: u64, width: u32) -> Option<u64> {
+    if x == 0 {
+        return None;
+    }
+    // Gosper's hack: moves the highest bit of the lowest block of ones one
+    // position up, and the rest of the block to the bottom.
+    let r = x.checked_add(x.blsi())?;
+    let y = r | (((x ^ r) >> 2) >> x.tzcnt());
+    if y & !low_bits(width) != 0 {
+        return None;
+    }
+    Some(y)
+}
+
+/// Successor of `x` among the words with `popcnt(x)` bits set.
+///
+/// Returns `None` if `x` is the largest word of its type with `popcnt(x)`
+/// bits set, which includes `x == 0`.
+///
+/// # Example
+///
+/// ```
+/// # use bitintr::comb;
+/// assert_eq!(comb::next_combination(0b0001_0111_u8), Some(0b0001_1011));
+/// assert_eq!(comb::next_combination(0b0001_1100_u8), Some(0b0010_0011));
+/// assert_eq!(comb::next_combination(0b1110_0000_u8), None);
+/// assert_eq!(comb::next_combination(0_u8), None);
+/// ```
+#[inline]
+pub fn next_combination<T: Narrow>(x: T) -> Option<T> {
+    next_(x.to_u64(), T::BITS).map(T::from_u64)
+}
+
+/// Predecessor of `x` among the words with `popcnt(x)` bits set.
+///
+/// Returns `None` if `x` is the smallest word with `popcnt(x)` bits set,
+/// that is, if its bits set are the low bits.
+///
+/// # Example
+///
+/// ```
+/// # use bitintr::comb;
+/// assert_eq!(comb::prev_combination(0b0001_1011_u8), Some(0b0001_0111));
+/// assert_eq!(comb::prev_combination(0b0010_0011_u8), Some(0b0001_1100));
+/// assert_eq!(comb::prev_combination(0b0000_0111_u8), None);
+/// assert_eq!(comb::prev_combination(0_u8), None);
+/// ```
+#[inline]
+pub fn prev_combination<T: Narrow>(x: T) -> Option<T> {
+    // Complementing the bits reverses the order and keeps the number of
+    // bits set constant:
+    let mask = low_bits(T::BITS);
+    next_(!x.to_u64() & mask, T::BITS).map(|y| T::from_u64(!y & mask))
+}
+
+/// Rank of `x` among the words with `popcnt(x)` bits set.
+///
+/// Returns the number of words smaller than `x` with the same number of bits
+/// set as `x`.
+///
+/// # Example
+///
+/// ```
+/// # use bitintr::comb;
+/// assert_eq!(comb::rank(0b0111_u8), 0);
+/// assert_eq!(comb::rank(0b1011_u8), 1);
+/// assert_eq!(comb::rank(0b1110_0000_u8), 55);
+/// assert_eq!(comb::rank(u64::MAX << 32), 1_832_624_140_942_590_533);
+/// ```
+#[inline]
+pub fn rank<T: Narrow>(x: T) -> u64 {
+    let mut x = x.to_u64();
+    let mut r = 0;
+    let mut j = 1;
+    while x != 0 {
+        r += BINOMIAL[x.tzcnt() as usize][j];
+        x &= x - 1;
+        j += 1;
+    }
+    r
+}
+
+/// Word with `k` bits set of rank `rank`.
+///
+/// This is the inverse of [`rank`](fn.rank.html). Returns `None` if `rank`
+/// is not smaller than the number of words of type `T` with `k` bits set.
+///
+/// # Example
+///
+/// ```
+/// # use bitintr::comb;
+/// assert_eq!(comb::unrank::<u8>(3, 0), Some(0b0111));
+/// assert_eq!(comb::unrank::<u8>(3, 55), Some(0b1110_0000));
+/// assert_eq!(comb::unrank::<u8>(3, 56), None);
+/// assert_eq!(comb::unrank::<u8>(9, 0), None);
+/// ```
+#[inline]
+pub fn unrank<T: Narrow>(k: u32, rank: u64) -> Option<T> {
+    if k > T::BITS || rank >= BINOMIAL[T::BITS as usize][k as usize] {
+        return None;
+    }
+    let mut r = rank;
+    let mut x = 0_u64;
+    // Position of the highest bit not yet considered:
+    let mut c = T::BITS as usize;
+    for j in (1..=k as usize).rev() {
+        // The largest `c` with `C(c, j) <= r`, which is at least `j - 1`
+        // since `C(j - 1, j) == 0`:
+        c -= 1;
+        while BINOMIAL[c][j] > r {
+            c -= 1;
+        }
+        x |= 1 << c;
+        r -= BINOMIAL[c][j];
+    }
+    Some(T::from_u64(x))
+}
+
+/// Iterator over the words of `n` bits with `k` bits set.
+///
+/// Yields the words in increasing order, or in decreasing order when
+/// iterated from the back. [`nth`](#method.nth) is computed with
+/// [`unrank`](fn.unrank.html).
+///
+/// The number of words is `C(n, k)`, which might not fit in a `usize`, so
+/// this iterator is not an `ExactSizeIterator`, but its `size_hint` is
+/// exact whenever it does.
+///
+/// # Example
+///
+/// ```
+/// # use bitintr::comb::*;
+/// let mut it = Combinations::<u16>::new(5, 3);
+/// assert_eq!(it.size_hint(), (10, Some(10)));
+/// assert_eq!(it.next(), Some(0b0_0111));
+/// assert_eq!(it.next_back(), Some(0b1_1100));
+/// assert_eq!(it.nth(1), Some(0b0_1101));
+///
+/// let mut it = Combinations::<u64>::new(64, 32);
+/// assert_eq!(it.nth(1 << 60), unrank(32, 1 << 60));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Combinations<T> {
+    /// Next combination from the front.
+    front: u64,
+    /// Next combination from the back.
+    back: u64,
+    done: bool,
+    _word: PhantomData<T>,
+}
+
+impl<T: Narrow> Combinations<T> {
+    /// Iterates over the words of `n` bits with `k` bits set.
+    ///
+    /// The iterator is empty if `k > n`.
+    ///
+    /// # Panics
+    ///
+    /// If `n` is larger than the bit width of `T`.
+    #[inline]
+    pub fn new(n: u32, k: u32) -> Self {
+        assert!(n <= T::BITS, "n = {} exceeds the bit width of the word", n);
+        let (front, back) = if k <= n {
+            (low_bits(k), low_bits(n) & !low_bits(n - k))
+        } else {
+            (0, 0)
+        };
+        Self {
+            front,
+            back,
+            done: k > n,
+            _word: PhantomData,
+        }
+    }
+}
+
+impl<T: Narrow> Iterator for Combinations<T> {
+    type Item = T;
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        if self.done {
+            return None;
+        }
+        let x = self.front;
+        if x == self.back {
+            self.done = true;
+        } else {
+            // `x < back`, so its successor fits in `T`:
+            self.front = next_(x, T::BITS).unwrap();
+        }
+        Some(T::from_u64(x))
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+        let n = rank(self.back) - rank(self.front);
+        match usize::try_from(n).ok().and_then(|n| n.checked_add(1)) {
+            Some(n) => (n, Some(n)),
+            None => (usize::MAX, None),
+        }
+    }
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<T> {
+        if self.done {
+            return None;
+        }
+        let r = u64::try_from(n)
+            .ok()
+            .and_then(|n| rank(self.front).checked_add(n));
+        match r {
+            Some(r) if r <= rank(self.back) => {
+                let k = self.front.popcnt() as u32;
+                self.front = unrank::<u64>(k, r).unwrap();
+                self.next()
+            }
+            _ => {
+                self.done = true;
+                None
+            }
+        }
+    }
+    #[inline]
+    fn last(mut self) -> Option<T> {
+        self.next_back()
+    }
+}
+
+impl<T: Narrow> DoubleEndedIterator for Combinations<T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<T> {
+        if self.done {
+            return None;
+        }
+        let x = self.back;
+        if x == self.front {
+            self.done = true;
+        } else {
+            self.back = prev_combination(x).unwrap();
+        }
+        Some(T::from_u64(x))
+    }
+}
+
+impl<T: Narrow> FusedIterator for Combinations<T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check<T: Narrow + core::fmt::Debug>(n: u32, k: u32) {
+        let mask = low_bits(n);
+        let mut it = Combinations::<T>::new(n, k);
+        let mut count = 0;
+        let mut prev = None;
+        for x in (0..=mask).filter(|x| x.popcnt() as u32 == k) {
+            let t = T::from_u64(x);
+            assert_eq!(rank(t), count, "{:#b}", x);
+            assert_eq!(unrank::<T>(k, count), Some(t));
+            if n == T::BITS {
+                if let Some(p) = prev {
+                    assert_eq!(next_combination(p), Some(t));
+                }
+                assert_eq!(prev_combination(t), prev);
+            }
+            let left = (C(n, k) - count) as usize;
+            assert_eq!(it.size_hint(), (left, Some(left)));
+            assert_eq!(it.next(), Some(t));
+            prev = Some(t);
+            count += 1;
+        }
+        assert_eq!(it.next(), None);
+        assert_eq!(count, C(n, k));
+        if n == T::BITS {
+            assert_eq!(prev.and_then(next_combination), None);
+            assert_eq!(unrank::<T>(k, count), None);
+        }
+
+        let all = Combinations::<T>::new(n, k);
+        let rev = (0..=mask).rev().filter(|x| x.popcnt() as u32 == k);
+        assert!(all.clone().rev().eq(rev.map(T::from_u64)));
+        let mut seq = all.clone();
+        for i in 0..=count as usize + 1 {
+            assert_eq!(all.clone().nth(i), seq.next());
+        }
+        // `nth` stops at the back of the iterator:
+        let mut it = all;
+        if it.next_back().is_some() {
+            assert_eq!(it.nth(count as usize - 1), None);
+            assert_eq!(it.next(), None);
+        }
+    }
+
+    #[allow(non_snake_case)]
+    fn C(n: u32, k: u32) -> u64 {
+        BINOMIAL[n as usize][k as usize]
+    }
+
+    #[test]
+    fn brute_force() {
+        for n in 0..=8 {
+            for k in 0..=n + 1 {
+                check::<u8>(n, k);
+            }
+        }
+        for k in 0..=17 {
+            check::<u16>(16, k);
+        }
+        check::<u32>(12, 6);
+        check::<u64>(12, 5);
+    }
+
+    #[test]
+    fn wide() {
+        assert_eq!(BINOMIAL[64][32], 1_832_624_140_942_590_534);
+        let mut it = Combinations::<u64>::new(64, 32);
+        assert_eq!(it.next_back(), Some(u64::MAX << 32));
+        assert_eq!(rank(u64::MAX << 32), BINOMIAL[64][32] - 1);
+        assert_eq!(it.nth(2), unrank(32, 2));
+        assert_eq!(next_combination(u64::MAX << 32), None);
+        assert_eq!(prev_combination(u64::MAX >> 32), None);
+        assert_eq!(unrank::<u64>(64, 0), Some(u64::MAX));
+        assert_eq!(Combinations::<u64>::new(64, 64).count(), 1);
+        assert!(Combinations::<u64>::new(64, 0).eq(core::iter::once(0)));
+    }
+}
@@ -0,0 +1,341 @@
+//! crc
+
+use crate::{Clmul, Rbit};
+
+/// Cyclic redundancy check with an arbitrary polynomial.
+///
+/// Computes the CRC of `WIDTH` bits, with `1 <= WIDTH <= 64`, whose
+/// generator polynomial is `x^WIDTH + POLY`, where `POLY` is written in the
+/// normal (not reflected) form, like in the tables of the CRC catalogue.
+/// If `REFLECT` is `true`, both the input bytes and the CRC are reflected,
+/// that is, the least significant bit of each byte is processed first.
+///
+/// Like [`Crc32c`](trait.Crc32c.html), [`update`](#method.update) neither
+/// initializes nor inverts the CRC: a catalogue CRC with parameters `init`
+/// and `xorout` is `update(init, message) ^ xorout`.
+///
+/// All constants are computed at compile-time. If a carry-less multiply
+/// instruction is available (see [`Clmul`](trait.Clmul.html)), messages are
+/// processed 16 bytes at a time by folding them with carry-less
+/// multiplications, followed by a Barrett reduction. Otherwise they are
+/// processed 8 bytes at a time with lookup tables (slicing-by-8).
+///
+/// # Example
+///
+/// ```
+/// # use bitintr::*;
+/// type Crc16Ibm3740 = Crc<0x1021, 16, false>;
+/// type Crc24OpenPgp = Crc<0x86_4CFB, 24, false>;
+/// type Crc32IsoHdlc = Crc<0x04C1_1DB7, 32, true>;
+/// type Crc64Ecma182 = Crc<0x42F0_E1EB_A9EA_3693, 64, false>;
+/// type Crc64Xz = Crc<0x42F0_E1EB_A9EA_3693, 64, true>;
+///
+/// let check = b"123456789";
+/// assert_eq!(Crc16Ibm3740::update(0xFFFF, check), 0x29B1);
+/// assert_eq!(Crc24OpenPgp::update(0xB7_04CE, check), 0x21_CF02);
+/// assert_eq!(
+///     Crc32IsoHdlc::update(0xFFFF_FFFF, check) ^ 0xFFFF_FFFF,
+///     0xCBF4_3926
+/// );
+/// assert_eq!(Crc64Ecma182::update(0, check), 0x6C40_DF5F_0B49_7347);
+/// assert_eq!(!Crc64Xz::update(!0, check), 0x995D_C9BB_DF19_39FA);
+///
+/// // Updates can be split at any byte:
+/// let crc = Crc64Xz::update(!0, b"1234");
+/// assert_eq!(!Crc64Xz::update(crc, b"56789"), 0x995D_C9BB_DF19_39FA);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Crc<const POLY: u64, const WIDTH: u32, const REFLECT: bool>;
+
+// The CRC is computed with a 64-bit register in which the `WIDTH`-bit CRC
+// is left-aligned and the first bit of the message is the most significant
+// one. This register is the remainder modulo the polynomial
+// `Q = (x^WIDTH + POLY) * x^(64 - WIDTH) = x^64 + q`.
+
+/// Computes `x^n mod Q`.
+const fn xpow_mod(n: u32, q: u64) -> u64 {
+    let mut r = 1_u64;
+    let mut i = 0;
+    while i < n {
+        r = (r << 1) ^ (q & 0_u64.wrapping_sub(r >> 63));
+        i += 1;
+    }
+    r
+}
+
+/// Computes the low 64 bits of `floor(x^128 / Q)`.
+const fn barrett_mu(q: u64) -> u64 {
+    let mut m = 0;
+    let mut rem = q;
+    let mut i = 64;
+    while i > 0 {
+        i -= 1;
+        let bit = rem >> 63;
+        m |= bit << i;
+        rem = (rem << 1) ^ (q & 0_u64.wrapping_sub(bit));
+    }
+    m
+}
+
+/// Computes the slicing-by-8 lookup tables: `t[k][b]` is the register after
+/// processing the byte `b` followed by `k` zero bytes.
+const fn slicing_tables(q: u64) -> [[u64; 256]; 8] {
+    let mut t = [[0; 256]; 8];
+    let mut b = 0;
+    while b < 256 {
+        let mut r = (b as u64) << 56;
+        let mut i = 0;
+        while i < 8 {
+            r = (r << 1) ^ (q & 0_u64.wrapping_sub(r >> 63));
+            i += 1;
+        }
+        t[0][b] = r;
+        b += 1;
+    }
+    let mut k = 1;
+    while k < 8 {
+        let mut b = 0;
+        while b < 256 {
+            let r = t[k - 1][b];
+            t[k][b] = (r << 8) ^ t[0][(r >> 56) as usize];
+            b += 1;
+        }
+        k += 1;
+    }
+    t
+}
+
+impl<const POLY: u64, const WIDTH: u32, const REFLECT: bool>
+    Crc<POLY, WIDTH, REFLECT>
+{
+    const VALID: () = assert!(
+        WIDTH >= 1 && WIDTH <= 64 && (WIDTH == 64 || POLY >> WIDTH == 0),
+        "the width must be in [1, 64] and the polynomial must fit in it"
+    );
+    const MASK: u64 = u64::MAX >> (64 - WIDTH);
+    const Q: u64 = POLY << (64 - WIDTH);
+    const K128: u64 = xpow_mod(128, Self::Q);
+    const K192: u64 = xpow_mod(192, Self::Q);
+    const MU: u64 = barrett_mu(Self::Q);
+    const TABLES: &'static [[u64; 256]; 8] = &slicing_tables(Self::Q);
+
+    /// Updates the CRC `crc` with the bytes of `data`.
+    ///
+    /// Only the `WIDTH` least significant bits of `crc` are used.
+    #[inline]
+    pub fn update(crc: u64, data: &[u8]) -> u64 {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::VALID;
+        let crc = crc & Self::MASK;
+        let reg = if REFLECT {
+            crc.rbit()
+        } else {
+            crc << (64 - WIDTH)
+        };
+        let reg = if cfg!(any(
+            all(target_arch = "x86_64", target_feature = "pclmulqdq"),
+            all(target_arch = "aarch64", target_feature = "aes")
+        )) {
+            Self::update_clmul(reg, data)
+        } else {
+            Self::update_table(reg, data)
+        };
+        if REFLECT {
+            reg.rbit()
+        } else {
+            reg >> (64 - WIDTH)
+        }
+    }
+
+    /// Loads 8 bytes into a word whose most significant bit is the first
+    /// bit of the message.
+    #[inline]
+    fn word(bytes: &[u8]) -> u64 {
+        let mut w = [0; 8];
+        w.copy_from_slice(bytes);
+        if REFLECT {
+            u64::from_le_bytes(w).rbit()
+        } else {
+            u64::from_be_bytes(w)
+        }
+    }
+
+    /// Loads a byte whose most significant bit is the first bit of the
+    /// message.
+    #[inline]
+    fn byte(b: u8) -> u8 {
+        if REFLECT {
+            b.rbit()
+        } else {
+            b
+        }
+    }
+
+    /// Computes `(hi * x^64 + lo) mod Q`.
+    #[inline]
+    fn barrett(hi: u64, lo: u64) -> u64 {
+        let (_, q) = hi.clmul(Self::MU);
+        let (r, _) = (q ^ hi).clmul(Self::Q);
+        lo ^ r
+    }
+
+    fn update_clmul(mut reg: u64, data: &[u8]) -> u64 {
+        let mut blocks = data.chunks_exact(16);
+        if let Some(b) = blocks.next() {
+            // The message is accumulated in `(hi, lo)`, such that the
+            // register is `(hi * x^128 + lo * x^64) mod Q`.
+            let mut hi = reg ^ Self::word(&b[..8]);
+            let mut lo = Self::word(&b[8..]);
+            for b in blocks.by_ref() {
+                let (l0, h0) = hi.clmul(Self::K192);
+                let (l1, h1) = lo.clmul(Self::K128);
+                hi = h0 ^ h1 ^ Self::word(&b[..8]);
+                lo = l0 ^ l1 ^ Self::word(&b[8..]);
+            }
+            let (l, h) = hi.clmul(Self::K128);
+            reg = Self::barrett(h ^ lo, l);
+        }
+        let mut words = blocks.remainder().chunks_exact(8);
+        for w in words.by_ref() {
+            reg = Self::barrett(reg ^ Self::word(w), 0);
+        }
+        for &b in words.remainder() {
+            let r = reg ^ (u64::from(Self::byte(b)) << 56);
+            reg = Self::barrett(r >> 56, r << 8);
+        }
+        reg
+    }
+
+    fn update_table(mut reg: u64, data: &[u8]) -> u64 {
+        let t = Self::TABLES;
+        let mut words = data.chunks_exact(8);
+        for w in words.by_ref() {
+            let r = reg ^ Self::word(w);
+            reg = t[7][(r >> 56) as usize]
+                ^ t[6][(r >> 48) as u8 as usize]
+                ^ t[5][(r >> 40) as u8 as usize]
+                ^ t[4][(r >> 32) as u8 as usize]
+                ^ t[3][(r >> 24) as u8 as usize]
+                ^ t[2][(r >> 16) as u8 as usize]
+                ^ t[1][(r >> 8) as u8 as usize]
+                ^ t[0][r as u8 as usize];
+        }
+        for &b in words.remainder() {
+            let i = ((reg >> 56) as u8 ^ Self::byte(b)) as usize;
+            reg = (reg << 8) ^ t[0][i];
+        }
+        reg
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{xorshift64, SEED};
+
+    /// Bit-at-a-time CRC, with the bits of the reflected CRCs processed
+    /// from the least significant one.
+    fn crc_naive(
+        poly: u64,
+        width: u32,
+        reflect: bool,
+        crc: u64,
+        data: &[u8],
+    ) -> u64 {
+        let mask = u64::MAX >> (64 - width);
+        let mut crc = crc & mask;
+        if reflect {
+            let rpoly = poly.rbit() >> (64 - width);
+            for &b in data {
+                crc ^= u64::from(b);
+                for _ in 0..8 {
+                    crc = (crc >> 1) ^ if crc & 1 == 1 { rpoly } else { 0 };
+                }
+            }
+        } else {
+            let top = 1 << (width - 1);
+            for &b in data {
+                for i in (0..8).rev() {
+                    let bit = ((b >> i) & 1) == 1;
+                    let msb = crc & top != 0;
+                    crc = (crc << 1) & mask;
+                    if bit != msb {
+                        crc ^= poly;
+                    }
+                }
+            }
+        }
+        crc
+    }
+
+    macro_rules! check {
+        ($poly:expr, $width:expr, $reflect:expr) => {{
+            type C = Crc<$poly, $width, $reflect>;
+            let mut data = [0_u8; 100];
+            let mut next = xorshift64(SEED);
+            for b in data.iter_mut() {
+                *b = next() as u8;
+            }
+            for len in 0..=data.len() {
+                for &crc in &[0, 1, !0, 0xDEAD_BEEF_F00D_CAFE] {
+                    let m = &data[..len];
+                    let expected = crc_naive($poly, $width, $reflect, crc, m);
+                    let reg = if $reflect {
+                        (crc & C::MASK).rbit()
+                    } else {
+                        (crc & C::MASK) << (64 - $width)
+                    };
+                    let reg_out = if $reflect {
+                        expected.rbit()
+                    } else {
+                        expected << (64 - $width)
+                    };
+                    assert_eq!(C::update(crc, m), expected, "len = {}", len);
+                    assert_eq!(C::update_clmul(reg, m), reg_out);
+                    assert_eq!(C::update_table(reg, m), reg_out);
+                }
+            }
+        }};
+    }
+
+    #[test]
+    fn naive() {
+        check!(0x07, 8, false);
+        check!(0x1D, 8, true);
+        check!(0x1021, 16, false);
+        check!(0x1021, 16, true);
+        check!(0x8005, 16, true);
+        check!(0x86_4CFB, 24, false);
+        check!(0x005D_6DCB, 24, true);
+        check!(0x04C1_1DB7, 32, false);
+        check!(0x04C1_1DB7, 32, true);
+        check!(0x1EDC_6F41, 32, true);
+        check!(0x3, 5, false);
+        check!(0x15, 7, true);
+        check!(0x0000_0000_0000_001B, 64, true);
+        check!(0x42F0_E1EB_A9EA_3693, 64, false);
+        check!(0x42F0_E1EB_A9EA_3693, 64, true);
+    }
+
+    #[test]
+    fn catalogue() {
+        use crate::{Crc32, Crc32c};
+        let check = b"123456789";
+        assert_eq!(Crc::<0x07, 8, false>::update(0, check), 0xF4);
+        assert_eq!(Crc::<0x1021, 16, true>::update(0, check), 0x2189);
+        assert_eq!(Crc::<0x8005, 16, true>::update(0, check), 0xBB3D);
+        assert_eq!(
+            !Crc::<0x04C1_1DB7, 32, false>::update(!0, check) & 0xFFFF_FFFF,
+            0xFC89_1918
+        );
+        let data = [0x5A_u8; 77];
+        assert_eq!(
+            Crc::<0x04C1_1DB7, 32, true>::update(!0, &data),
+            u64::from(u32::MAX.crc32(&data[..]))
+        );
+        assert_eq!(
+            Crc::<0x1EDC_6F41, 32, true>::update(!0, &data),
+            u64::from(u32::MAX.crc32c(&data[..]))
+        );
+    }
+}
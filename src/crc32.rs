@@ -0,0 +1,133 @@
+//! crc32
+
+/// Accumulate CRC-32 (IEEE 802.3)
+pub trait Crc32<T> {
+    /// Accumulate CRC-32 (IEEE 802.3).
+    ///
+    /// Like [`Crc32c`](trait.Crc32c.html), but using the (reflected)
+    /// polynomial `0xEDB88320` of Ethernet, zlib and PNG. The CRC-32 of a
+    /// message is `!u32::MAX.crc32(message)`.
+    ///
+    /// # Instructions
+    ///
+    /// - [`CRC32B`, `CRC32H`, `CRC32W`, `CRC32X`](http://infocenter.arm.
+    ///   com/help/index.jsp?topic=/com.arm.doc.ddi0487a.k_10775/index.html):
+    ///   - Description: CRC32 checksum.
+    ///   - Architecture: ARMv8.
+    ///   - Instruction set: CRC.
+    ///   - Registers: 8/16/32/64 bit.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bitintr::*;
+    /// assert_eq!(!u32::MAX.crc32(&b"123456789"[..]), 0xCBF4_3926);
+    ///
+    /// let crc = u32::MAX.crc32(0x0403_0201_u32);
+    /// assert_eq!(crc, u32::MAX.crc32(&[1_u8, 2, 3, 4][..]));
+    /// assert_eq!(crc, u32::MAX.crc32(0x0201_u16).crc32(0x0403_u16));
+    /// ```
+    fn crc32(self, data: T) -> Self;
+}
+
+/// Byte-wise lookup table of the reflected polynomial `0xEDB88320`.
+#[allow(dead_code)]
+static TABLE: [u32; 256] = crate::crc32c::crc32_table(0xEDB8_8320);
+
+macro_rules! crc32_impl {
+    ($ty:ty) => {
+        #[inline]
+        fn crc32_(mut crc: u32, data: $ty) -> u32 {
+            for &b in data.to_le_bytes().iter() {
+                crc = TABLE[((crc as u8) ^ b) as usize] ^ (crc >> 8);
+            }
+            crc
+        }
+    };
+    ($ty:ty, $arm:ident) => {
+        cfg_if! {
+            if #[cfg(all(
+                target_arch = "aarch64",
+                target_feature = "crc"
+            ))] {
+                #[inline]
+                #[target_feature(enable = "crc")]
+                unsafe fn crc32_(crc: u32, data: $ty) -> u32 {
+                    crate::arch::$arm(crc, data)
+                }
+            } else {
+                crc32_impl!($ty);
+            }
+        }
+    };
+}
+
+macro_rules! impl_crc32 {
+    ($id:ident $(,$args:ident)*) => {
+        impl Crc32<$id> for u32 {
+            #[inline]
+            #[allow(unused_unsafe)]
+            fn crc32(self, data: $id) -> Self {
+                crc32_impl!($id $(,$args)*);
+                // UNSAFETY: this is always safe, because
+                // the unsafe `#[target_feature]` function
+                // is only generated when the feature is
+                // statically-enabled at compile-time.
+                unsafe { crc32_(self, data) }
+            }
+        }
+    };
+}
+
+impl_crc32!(u8, __crc32b);
+impl_crc32!(u16, __crc32h);
+impl_crc32!(u32, __crc32w);
+impl_crc32!(u64, __crc32d);
+
+impl<'a> Crc32<&'a [u8]> for u32 {
+    #[inline]
+    fn crc32(self, data: &'a [u8]) -> Self {
+        let mut crc = self;
+        let chunks = data.chunks_exact(8);
+        let tail = chunks.remainder();
+        for c in chunks {
+            let mut word = [0; 8];
+            word.copy_from_slice(c);
+            crc = crc.crc32(u64::from_le_bytes(word));
+        }
+        for &b in tail {
+            crc = crc.crc32(b);
+        }
+        crc
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Crc32;
+
+    // Check values of the CRC catalogue.
+    #[test]
+    fn known_vectors() {
+        let crc32 = |m: &[u8]| !u32::MAX.crc32(m);
+
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(crc32(b"a"), 0xE8B7_BE43);
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+        assert_eq!(
+            crc32(b"The quick brown fox jumps over the lazy dog"),
+            0x414F_A339
+        );
+    }
+
+    #[test]
+    fn word_sizes() {
+        let x = 0x0123_4567_89AB_CDEF_u64;
+        let bytes = x.to_le_bytes();
+        for &crc in &[0, 1, 0xDEAD_BEEF, u32::MAX] {
+            let c = crc.crc32(&bytes[..]);
+            assert_eq!(crc.crc32(x), c);
+            assert_eq!(crc.crc32(x as u16).crc32(&bytes[2..]), c);
+        }
+    }
+}
@@ -0,0 +1,190 @@
+//! crc32c
+
+/// Accumulate CRC-32C (Castagnoli)
+pub trait Crc32c<T> {
+    /// Accumulate CRC-32C (Castagnoli).
+    ///
+    /// Updates the CRC `self` with the bytes of `data`, and returns the
+    /// updated CRC. The bytes of integer words are processed in
+    /// little-endian order, so that accumulating a `u64` is the same as
+    /// accumulating its eight bytes, and `data` can also be a `&[u8]`
+    /// slice.
+    ///
+    /// The CRC uses the (reflected) Castagnoli polynomial `0x82F63B78`.
+    /// Like the instructions, it does not invert the CRC before or after
+    /// the update: the CRC-32C of a message is
+    /// `!u32::MAX.crc32c(message)`.
+    ///
+    /// # Instructions
+    ///
+    /// - [`CRC32`](http://www.felixcloutier.com/x86/CRC32.html):
+    ///   - Description: Accumulate CRC32C value.
+    ///   - Architecture: x86.
+    ///   - Instruction set: SSE4.2.
+    ///   - Registers: 8/16/32/64 bit.
+    /// - [`CRC32CB`, `CRC32CH`, `CRC32CW`, `CRC32CX`](http://infocenter.arm.
+    ///   com/help/index.jsp?topic=/com.arm.doc.ddi0487a.k_10775/index.html):
+    ///   - Description: CRC32C checksum.
+    ///   - Architecture: ARMv8.
+    ///   - Instruction set: CRC.
+    ///   - Registers: 8/16/32/64 bit.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bitintr::*;
+    /// assert_eq!(!u32::MAX.crc32c(&b"123456789"[..]), 0xE306_9283);
+    ///
+    /// let crc = u32::MAX.crc32c(0x0403_0201_u32);
+    /// assert_eq!(crc, u32::MAX.crc32c(&[1_u8, 2, 3, 4][..]));
+    /// assert_eq!(crc, u32::MAX.crc32c(0x0201_u16).crc32c(0x0403_u16));
+    /// ```
+    fn crc32c(self, data: T) -> Self;
+}
+
+/// Byte-wise lookup table of the reflected polynomial `0x82F63B78`.
+#[allow(dead_code)]
+static TABLE: [u32; 256] = crc32_table(0x82F6_3B78);
+
+/// Computes the byte-wise lookup table of the reflected CRC-32
+/// polynomial `poly`.
+pub(crate) const fn crc32_table(poly: u32) -> [u32; 256] {
+    let mut t = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut k = 0;
+        while k < 8 {
+            crc = (crc >> 1) ^ (poly & 0_u32.wrapping_sub(crc & 1));
+            k += 1;
+        }
+        t[i] = crc;
+        i += 1;
+    }
+    t
+}
+
+macro_rules! crc32c_impl {
+    ($ty:ty) => {
+        #[inline]
+        fn crc32c_(mut crc: u32, data: $ty) -> u32 {
+            for &b in data.to_le_bytes().iter() {
+                crc = TABLE[((crc as u8) ^ b) as usize] ^ (crc >> 8);
+            }
+            crc
+        }
+    };
+    ($ty:ty, $x86:ident, $arm:ident) => {
+        cfg_if! {
+            if #[cfg(all(
+                any(target_arch = "x86", target_arch = "x86_64"),
+                target_feature = "sse4.2"
+            ))] {
+                #[inline]
+                #[target_feature(enable = "sse4.2")]
+                unsafe fn crc32c_(crc: u32, data: $ty) -> u32 {
+                    crate::arch::$x86(crc as _, data) as _
+                }
+            } else if #[cfg(all(
+                target_arch = "aarch64",
+                target_feature = "crc"
+            ))] {
+                #[inline]
+                #[target_feature(enable = "crc")]
+                unsafe fn crc32c_(crc: u32, data: $ty) -> u32 {
+                    crate::arch::$arm(crc, data)
+                }
+            } else {
+                crc32c_impl!($ty);
+            }
+        }
+    };
+}
+
+macro_rules! impl_crc32c {
+    ($id:ident $(,$args:ident)*) => {
+        impl Crc32c<$id> for u32 {
+            #[inline]
+            #[allow(unused_unsafe)]
+            fn crc32c(self, data: $id) -> Self {
+                crc32c_impl!($id $(,$args)*);
+                // UNSAFETY: this is always safe, because
+                // the unsafe `#[target_feature]` function
+                // is only generated when the feature is
+                // statically-enabled at compile-time.
+                unsafe { crc32c_(self, data) }
+            }
+        }
+    };
+}
+
+impl_crc32c!(u8, _mm_crc32_u8, __crc32cb);
+impl_crc32c!(u16, _mm_crc32_u16, __crc32ch);
+impl_crc32c!(u32, _mm_crc32_u32, __crc32cw);
+
+cfg_if! {
+    if #[cfg(target_arch = "x86")] {
+        // There is no 64-bit CRC32 instruction in 32-bit mode:
+        impl Crc32c<u64> for u32 {
+            #[inline]
+            fn crc32c(self, data: u64) -> Self {
+                self.crc32c(data as u32).crc32c((data >> 32) as u32)
+            }
+        }
+    } else {
+        impl_crc32c!(u64, _mm_crc32_u64, __crc32cd);
+    }
+}
+
+impl<'a> Crc32c<&'a [u8]> for u32 {
+    #[inline]
+    fn crc32c(self, data: &'a [u8]) -> Self {
+        let mut crc = self;
+        let chunks = data.chunks_exact(8);
+        let tail = chunks.remainder();
+        for c in chunks {
+            let mut word = [0; 8];
+            word.copy_from_slice(c);
+            crc = crc.crc32c(u64::from_le_bytes(word));
+        }
+        for &b in tail {
+            crc = crc.crc32c(b);
+        }
+        crc
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Crc32c;
+
+    // Check values of the CRC catalogue and RFC 3720 (iSCSI).
+    #[test]
+    fn known_vectors() {
+        let crc32c = |m: &[u8]| !u32::MAX.crc32c(m);
+
+        assert_eq!(crc32c(b""), 0);
+        assert_eq!(crc32c(b"a"), 0xC1D0_4330);
+        assert_eq!(crc32c(b"123456789"), 0xE306_9283);
+        assert_eq!(crc32c(&[0; 32]), 0x8A91_36AA);
+        assert_eq!(crc32c(&[0xFF; 32]), 0x62A8_AB43);
+        let mut inc = [0; 32];
+        for (i, b) in inc.iter_mut().enumerate() {
+            *b = i as u8;
+        }
+        assert_eq!(crc32c(&inc), 0x46DD_794E);
+        inc.reverse();
+        assert_eq!(crc32c(&inc), 0x113F_DB5C);
+    }
+
+    #[test]
+    fn word_sizes() {
+        let x = 0x0123_4567_89AB_CDEF_u64;
+        let bytes = x.to_le_bytes();
+        for &crc in &[0, 1, 0xDEAD_BEEF, u32::MAX] {
+            let c = crc.crc32c(&bytes[..]);
+            assert_eq!(crc.crc32c(x), c);
+            assert_eq!(crc.crc32c(x as u32).crc32c((x >> 32) as u32), c);
+        }
+    }
+}
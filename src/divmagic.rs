@@ -0,0 +1,321 @@
+//! divmagic
+
+use crate::{Divx, Lzcnt, Mulx};
+
+/// Division by an invariant integer.
+///
+/// Precomputes a "magic" multiplier and a shift for a divisor `d` so that
+/// each division by `d` is computed with the high half of a
+/// [`Mulx`](trait.Mulx.html) multiplication, an addition, and shifts,
+/// instead of with a division instruction. This pays off when dividing many
+/// integers by the same divisor that is not known at compile-time.
+///
+/// Quotients are rounded toward zero like the `/` operator. For signed
+/// integers, dividing `T::MIN` by `-1` wraps like `wrapping_div`.
+///
+/// See T. Granlund and P. L. Montgomery, "Division by Invariant Integers
+/// using Multiplication", and H. S. Warren, "Hacker's Delight", chapter 10.
+///
+/// # Example
+///
+/// ```
+/// # use bitintr::*;
+/// let d = DivMagic::<u32>::new(7);
+/// assert_eq!(d.div(100), 14);
+/// assert_eq!(d.rem(100), 2);
+/// assert!(d.divisible_by(98));
+/// assert!(!d.divisible_by(100));
+///
+/// let d = DivMagic::<i16>::new(-7);
+/// assert_eq!(d.div(100), -14);
+/// assert_eq!(d.rem(-100), -2);
+/// assert_eq!(DivMagic::<i16>::new(-1).div(i16::MIN), i16::MIN);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct DivMagic<T> {
+    d: T,
+    magic: T,
+    shift: u32,
+}
+
+macro_rules! impl_divmagic_common {
+    ($id:ident) => {
+        /// Returns the divisor.
+        #[inline]
+        pub fn divisor(&self) -> $id {
+            self.d
+        }
+
+        /// Computes the remainder of `n / d`, which has the sign of `n`.
+        #[inline]
+        pub fn rem(&self, n: $id) -> $id {
+            n.wrapping_sub(self.div(n).wrapping_mul(self.d))
+        }
+
+        /// Returns whether `n` is divisible by `d`.
+        #[inline]
+        pub fn divisible_by(&self, n: $id) -> bool {
+            self.rem(n) == 0
+        }
+    };
+}
+
+macro_rules! impl_udivmagic {
+    ($id:ident) => {
+        impl DivMagic<$id> {
+            /// Precomputes the magic numbers of the divisor `d`.
+            ///
+            /// # Panics
+            ///
+            /// If `d == 0`.
+            #[inline]
+            pub fn new(d: $id) -> Self {
+                const BIT_WIDTH: u32 =
+                    (crate::mem::size_of::<$id>() * 8) as u32;
+                assert!(d != 0, "attempt to divide by zero");
+                // l = ceil(log2(d))
+                let l = BIT_WIDTH - (d - 1).lzcnt() as u32;
+                // magic = floor(2^W * (2^l - d) / d) + 1, which is the
+                // multiplier `m = floor(2^(W + l) / d) + 1` minus `2^W`.
+                let hi =
+                    (1 as $id).checked_shl(l).unwrap_or(0).wrapping_sub(d);
+                let (q, _) = hi.divx(0, d).unwrap();
+                Self {
+                    d,
+                    magic: q.wrapping_add(1),
+                    shift: l,
+                }
+            }
+
+            /// Computes `n / d`.
+            #[inline]
+            pub fn div(&self, n: $id) -> $id {
+                let t = self.magic.mulhi(n);
+                // (t + (n - t) / 2) / 2^(l - 1), computed without
+                // overflowing the addition:
+                let (s1, s2) =
+                    (self.shift.min(1), self.shift.saturating_sub(1));
+                (t + ((n - t) >> s1)) >> s2
+            }
+
+            impl_divmagic_common!($id);
+        }
+    };
+}
+
+macro_rules! impl_sdivmagic {
+    ($id:ident, $uid:ident) => {
+        impl DivMagic<$id> {
+            /// Precomputes the magic numbers of the divisor `d`.
+            ///
+            /// # Panics
+            ///
+            /// If `d == 0`.
+            #[inline]
+            pub fn new(d: $id) -> Self {
+                const BIT_WIDTH: u32 =
+                    (crate::mem::size_of::<$id>() * 8) as u32;
+                assert!(d != 0, "attempt to divide by zero");
+                let ad = d.unsigned_abs();
+                // l = max(ceil(log2(|d|)), 1)
+                let l = (BIT_WIDTH - (ad - 1).lzcnt() as u32).max(1);
+                // magic = floor(2^(W + l - 1) / |d|) + 1 - 2^W, which is
+                // negative except for |d| == 1.
+                let magic = match ((1 as $uid) << (l - 1)).divx(0, ad) {
+                    Some((q, _)) => q.wrapping_add(1) as $id,
+                    None => 1,
+                };
+                Self {
+                    d,
+                    magic,
+                    shift: l - 1,
+                }
+            }
+
+            /// Computes `n / d`, rounded toward zero.
+            #[inline]
+            pub fn div(&self, n: $id) -> $id {
+                const BIT_WIDTH: u32 =
+                    (crate::mem::size_of::<$id>() * 8) as u32;
+                let hi = self.magic.mulhi(n);
+                let q = n.wrapping_add(hi) >> self.shift;
+                // Rounds toward zero and applies the sign of `d`:
+                let q = q.wrapping_sub(n >> (BIT_WIDTH - 1));
+                let sign = self.d >> (BIT_WIDTH - 1);
+                (q ^ sign).wrapping_sub(sign)
+            }
+
+            impl_divmagic_common!($id);
+        }
+    };
+}
+
+impl_udivmagic!(u8);
+impl_udivmagic!(u16);
+impl_udivmagic!(u32);
+impl_udivmagic!(u64);
+impl_sdivmagic!(i8, u8);
+impl_sdivmagic!(i16, u16);
+impl_sdivmagic!(i32, u32);
+impl_sdivmagic!(i64, u64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{xorshift64, SEED};
+
+    macro_rules! exhaustive {
+        ($(#[$attr:meta])* $name:ident, $id:ident) => {
+            #[test]
+            $(#[$attr])*
+            fn $name() {
+                for d in $id::MIN..=$id::MAX {
+                    if d == 0 {
+                        continue;
+                    }
+                    let m = DivMagic::<$id>::new(d);
+                    for n in $id::MIN..=$id::MAX {
+                        assert_eq!(
+                            m.div(n),
+                            n.wrapping_div(d),
+                            "{} / {}",
+                            n,
+                            d
+                        );
+                        assert_eq!(
+                            m.rem(n),
+                            n.wrapping_rem(d),
+                            "{} % {}",
+                            n,
+                            d
+                        );
+                        assert_eq!(m.divisible_by(n), n.wrapping_rem(d) == 0);
+                    }
+                }
+            }
+        };
+    }
+
+    exhaustive!(u8_exhaustive, u8);
+    exhaustive!(i8_exhaustive, i8);
+    // 2^32 pairs each, ignored in debug builds, where they are too slow:
+    exhaustive!(
+        #[cfg_attr(debug_assertions, ignore)]
+        u16_exhaustive,
+        u16
+    );
+    exhaustive!(
+        #[cfg_attr(debug_assertions, ignore)]
+        i16_exhaustive,
+        i16
+    );
+
+    macro_rules! all_dividends {
+        ($name:ident, $id:ident, $divisors:expr) => {
+            #[test]
+            fn $name() {
+                for &d in $divisors {
+                    let m = DivMagic::<$id>::new(d);
+                    for n in 0..=u16::MAX {
+                        let n = n as $id;
+                        assert_eq!(
+                            m.div(n),
+                            n.wrapping_div(d),
+                            "{} / {}",
+                            n,
+                            d
+                        );
+                        assert_eq!(
+                            m.rem(n),
+                            n.wrapping_rem(d),
+                            "{} % {}",
+                            n,
+                            d
+                        );
+                    }
+                }
+            }
+        };
+    }
+
+    // Quick checks for debug builds: all u16/i16 divisors against a sample
+    // of dividends, and a sample of divisors against all dividends.
+    macro_rules! exhaustive_divisors {
+        ($name:ident, $id:ident) => {
+            #[test]
+            fn $name() {
+                const DIVIDENDS: &[$id] = &[
+                    $id::MIN,
+                    $id::MIN + 1,
+                    0,
+                    1,
+                    2,
+                    3,
+                    1000,
+                    $id::MAX / 2,
+                    $id::MAX / 2 + 1,
+                    $id::MAX - 1,
+                    $id::MAX,
+                ];
+                for d in $id::MIN..=$id::MAX {
+                    if d == 0 {
+                        continue;
+                    }
+                    let m = DivMagic::<$id>::new(d);
+                    for &n in DIVIDENDS {
+                        assert_eq!(
+                            m.div(n),
+                            n.wrapping_div(d),
+                            "{} / {}",
+                            n,
+                            d
+                        );
+                        assert_eq!(
+                            m.rem(n),
+                            n.wrapping_rem(d),
+                            "{} % {}",
+                            n,
+                            d
+                        );
+                    }
+                }
+            }
+        };
+    }
+
+    exhaustive_divisors!(u16_divisors, u16);
+    exhaustive_divisors!(i16_divisors, i16);
+    all_dividends!(u16_dividends, u16, &[1, 2, 3, 7, 10, 641, 32_768, 65_535]);
+    all_dividends!(
+        i16_dividends,
+        i16,
+        &[1, -1, 3, -7, 10, 641, i16::MIN, i16::MAX]
+    );
+
+    #[test]
+    fn wide() {
+        let mut next = xorshift64(SEED);
+        for i in 0..256 {
+            // Divisors of every magnitude, including powers of two:
+            let d = match i % 4 {
+                0 => 1 << (i / 4),
+                _ => (next() >> (next() % 64)).max(1),
+            };
+            let (m64, mi64) =
+                (DivMagic::<u64>::new(d), DivMagic::<i64>::new(d as i64));
+            let (d32, di32) = ((d as u32).max(1), (d as i32).max(1));
+            let (m32, mi32) =
+                (DivMagic::<u32>::new(d32), DivMagic::<i32>::new(-di32));
+            for _ in 0..64 {
+                let n = next();
+                assert_eq!(m64.div(n), n / d);
+                assert_eq!(
+                    mi64.div(n as i64),
+                    (n as i64).wrapping_div(d as i64)
+                );
+                assert_eq!(m32.div(n as u32), n as u32 / d32);
+                assert_eq!(mi32.div(n as i32), (n as i32).wrapping_div(-di32));
+            }
+        }
+    }
+}
@@ -0,0 +1,285 @@
+//! divx
+
+/// Unsigned narrowing division of a double-word dividend.
+pub trait Divx: crate::marker::Sized {
+    /// Unsigned narrowing division of a double-word dividend.
+    ///
+    /// Divides the double-word dividend whose high half is `self` and whose
+    /// low half is `lo` by the divisor `d`, returning a pair `(quotient,
+    /// remainder)`. This is the inverse of [`Mulx`](trait.Mulx.html).
+    ///
+    /// Returns `None` if the quotient does not fit in a single word, that
+    /// is, if `self >= d`. This includes division by zero.
+    ///
+    /// # Instructions
+    ///
+    /// - [`DIV`](http://www.felixcloutier.com/x86/DIV.html):
+    ///   - Description: Unsigned divide.
+    ///   - Architecture: x86.
+    ///   - Registers: 8/16/32/64 bit.
+    ///
+    /// On other targets, 64-bit divisions are computed by multiplying with
+    /// an approximate reciprocal of the divisor (Möller-Granlund) instead of
+    /// calling the 128-bit division routine.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bitintr::*;
+    /// // 0x0123 / 0x10 == 0x12, with remainder 0x3:
+    /// assert_eq!(0x01_u8.divx(0x23, 0x10), Some((0x12, 0x3)));
+    /// // The quotient 0x0123 does not fit in a `u8`:
+    /// assert_eq!(0x01_u8.divx(0x23, 0x01), None);
+    /// assert_eq!(0x00_u8.divx(0x23, 0x00), None);
+    ///
+    /// // Round trip with `mulx`:
+    /// let (a, b) = (0x0123_4567_89AB_CDEF_u64, 0xFEDC_BA98_7654_3210_u64);
+    /// let (lo, hi) = a.mulx(b);
+    /// assert_eq!(hi.divx(lo, b), Some((a, 0)));
+    /// ```
+    fn divx(self, lo: Self, d: Self) -> Option<(Self, Self)>;
+}
+
+/// Computes the reciprocal `floor((2^128 - 1) / d) - 2^64` of a normalized
+/// divisor `d`, that is, one whose most significant bit is set.
+#[cfg_attr(target_arch = "x86_64", allow(dead_code))]
+#[inline]
+pub(crate) fn reciprocal_u64(d: u64) -> u64 {
+    // Initial 11-bit approximations `floor((2^19 - 3 * 2^8) / d9)` indexed
+    // by the 9 most significant bits `d9` of `d`.
+    const TABLE: [u16; 256] = {
+        let mut t = [0; 256];
+        let mut i = 0;
+        while i < 256 {
+            t[i] = (0x7_FD00 / (i + 256)) as u16;
+            i += 1;
+        }
+        t
+    };
+    debug_assert!(d >> 63 == 1);
+    let d0 = d & 1;
+    let d9 = d >> 55;
+    let d40 = (d >> 24) + 1;
+    let d63 = (d >> 1) + d0;
+    let v0 = u64::from(TABLE[(d9 - 256) as usize]);
+    // Newton iterations, each one doubling the number of correct bits:
+    let v1 = (v0 << 11) - ((v0 * v0 * d40) >> 40) - 1;
+    let v2 = (v1 << 13) + ((v1 * ((1 << 60) - v1 * d40)) >> 47);
+    let e = ((v2 >> 1) & 0_u64.wrapping_sub(d0))
+        .wrapping_sub(v2.wrapping_mul(d63));
+    let v3 = (v2 << 31)
+        .wrapping_add(((u128::from(v2) * u128::from(e)) >> 65) as u64);
+    // Final adjustment:
+    let p = u128::from(v3) * u128::from(d) + u128::from(d);
+    v3.wrapping_sub((p >> 64) as u64).wrapping_sub(d)
+}
+
+/// Divides `(u1, u0)` by the normalized divisor `d` with reciprocal `v`.
+///
+/// Requires `u1 < d`.
+#[cfg_attr(target_arch = "x86_64", allow(dead_code))]
+#[inline]
+pub(crate) fn div_preinv_u64(u1: u64, u0: u64, d: u64, v: u64) -> (u64, u64) {
+    let q = u128::from(v) * u128::from(u1)
+        + ((u128::from(u1) << 64) | u128::from(u0));
+    let (q1, q0) = (((q >> 64) as u64).wrapping_add(1), q as u64);
+    let mut q1 = q1;
+    let mut r = u0.wrapping_sub(q1.wrapping_mul(d));
+    if r > q0 {
+        q1 = q1.wrapping_sub(1);
+        r = r.wrapping_add(d);
+    }
+    if r >= d {
+        q1 += 1;
+        r -= d;
+    }
+    (q1, r)
+}
+
+macro_rules! divx_impl {
+    (u32) => {
+        cfg_if! {
+            if #[cfg(any(target_arch = "x86", target_arch = "x86_64"))] {
+                #[inline]
+                fn divx_(hi: u32, lo: u32, d: u32) -> (u32, u32) {
+                    let (q, r);
+                    // UNSAFETY: `hi < d`, so `DIV` does not fault.
+                    unsafe {
+                        core::arch::asm!(
+                            "div {d:e}",
+                            d = in(reg) d,
+                            inout("eax") lo => q,
+                            inout("edx") hi => r,
+                            options(pure, nomem, nostack),
+                        );
+                    }
+                    (q, r)
+                }
+            } else {
+                divx_impl!(u32, u64);
+            }
+        }
+    };
+    (u64) => {
+        cfg_if! {
+            if #[cfg(target_arch = "x86_64")] {
+                #[inline]
+                fn divx_(hi: u64, lo: u64, d: u64) -> (u64, u64) {
+                    let (q, r);
+                    // UNSAFETY: `hi < d`, so `DIV` does not fault.
+                    unsafe {
+                        core::arch::asm!(
+                            "div {d}",
+                            d = in(reg) d,
+                            inout("rax") lo => q,
+                            inout("rdx") hi => r,
+                            options(pure, nomem, nostack),
+                        );
+                    }
+                    (q, r)
+                }
+            } else {
+                #[inline]
+                fn divx_(hi: u64, lo: u64, d: u64) -> (u64, u64) {
+                    use crate::Fshl;
+                    let s = d.leading_zeros();
+                    let d = d << s;
+                    let (q, r) = div_preinv_u64(
+                        hi.fshl(lo, s),
+                        lo << s,
+                        d,
+                        reciprocal_u64(d),
+                    );
+                    (q, r >> s)
+                }
+            }
+        }
+    };
+    ($ty:ty, $wide:ty) => {
+        #[inline]
+        fn divx_(hi: $ty, lo: $ty, d: $ty) -> ($ty, $ty) {
+            const BIT_WIDTH: u32 = (crate::mem::size_of::<$ty>() * 8) as u32;
+            let n = (<$wide>::from(hi) << BIT_WIDTH) | <$wide>::from(lo);
+            let d = <$wide>::from(d);
+            ((n / d) as $ty, (n % d) as $ty)
+        }
+    };
+}
+
+macro_rules! impl_divx {
+    ($id:ident $(,$args:tt)*) => {
+        impl Divx for $id {
+            #[inline]
+            fn divx(self, lo: Self, d: Self) -> Option<(Self, Self)> {
+                divx_impl!($id $(,$args)*);
+                if self < d {
+                    Some(divx_(self, lo, d))
+                } else {
+                    None
+                }
+            }
+        }
+    };
+}
+
+impl_divx!(u8, u16);
+impl_divx!(u16, u32);
+impl_divx!(u32);
+impl_divx!(u64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{xorshift64, SEED};
+
+    fn check_u64(hi: u64, lo: u64, d: u64) {
+        let n = (u128::from(hi) << 64) | u128::from(lo);
+        let expected = if hi < d {
+            Some(((n / u128::from(d)) as u64, (n % u128::from(d)) as u64))
+        } else {
+            None
+        };
+        assert_eq!(hi.divx(lo, d), expected);
+        // The reciprocal method is also checked on targets that use `DIV`:
+        if let Some(expected) = expected {
+            use crate::Fshl;
+            let s = d.leading_zeros();
+            let dn = d << s;
+            let v = reciprocal_u64(dn);
+            assert_eq!(u128::from(v), u128::MAX / u128::from(dn) - (1 << 64));
+            let (q, r) = div_preinv_u64(hi.fshl(lo, s), lo << s, dn, v);
+            assert_eq!((q, r >> s), expected);
+        }
+    }
+
+    #[test]
+    fn divx_u8() {
+        for hi in 0..=u8::MAX {
+            for d in 0..=u8::MAX {
+                for &lo in &[0, 1, 0x7F, 0x80, 0xAB, u8::MAX] {
+                    let n = (u16::from(hi) << 8) | u16::from(lo);
+                    let expected = if hi < d {
+                        let d = u16::from(d);
+                        Some(((n / d) as u8, (n % d) as u8))
+                    } else {
+                        None
+                    };
+                    assert_eq!(hi.divx(lo, d), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn divx_u32() {
+        let words = [0, 1, 2, 3, 0x7FFF_FFFF, 0x8000_0000, 0xDEAD_BEEF, !0];
+        for &hi in &words {
+            for &lo in &words {
+                for &d in &words {
+                    let n = (u64::from(hi) << 32) | u64::from(lo);
+                    let expected = if hi < d {
+                        let d = u64::from(d);
+                        Some(((n / d) as u32, (n % d) as u32))
+                    } else {
+                        None
+                    };
+                    assert_eq!(hi.divx(lo, d), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn divx_u64() {
+        let mut words = [
+            0,
+            1,
+            2,
+            3,
+            0x7FFF_FFFF_FFFF_FFFF,
+            0x8000_0000_0000_0000,
+            0x8000_0000_0000_0001,
+            0xFFFF_FFFF,
+            1 << 32,
+            !0 - 1,
+            !0,
+            0,
+            0,
+            0,
+            0,
+            0,
+        ];
+        let mut next = xorshift64(SEED);
+        for w in words[11..].iter_mut() {
+            *w = next();
+        }
+        for &hi in &words {
+            for &lo in &words {
+                for &d in &words {
+                    check_u64(hi, lo, d);
+                    check_u64(hi >> 17, lo, d >> 17);
+                }
+            }
+        }
+    }
+}
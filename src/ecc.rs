@@ -0,0 +1,252 @@
+//! Hamming SEC-DED error correcting codes.
+//!
+//! Protects a payload of 8, 16, 32 or 64 bits with a check byte that allows
+//! correcting any single bit error (SEC) and detecting any double bit error
+//! (DED) in the payload and in the check byte, using the extended Hamming
+//! codes (13, 8), (22, 16), (39, 32) and (72, 64), respectively.
+//!
+//! The `r` low bits of the check byte are the Hamming check bits, where `r`
+//! is 4, 5, 6 and 7, respectively, and bit `r` is the parity of the whole
+//! codeword. The remaining bits of the check byte are zero.
+//!
+//! Every check bit is the parity of the payload bits selected by a mask,
+//! which is computed at compile-time.
+//!
+//! # Example
+//!
+//! ```
+//! # use bitintr::ecc::{self, Decoded};
+//! let data = 0x0123_4567_89AB_CDEF_u64;
+//! let check = ecc::encode(data);
+//!
+//! assert_eq!(ecc::decode(data, check), Decoded::Clean(data));
+//! // Single bit errors, either in the payload or in the check byte, are
+//! // corrected:
+//! assert_eq!(ecc::decode(data ^ (1 << 42), check), Decoded::Corrected(data));
+//! assert_eq!(ecc::decode(data, check ^ 0b10), Decoded::Corrected(data));
+//! // Double bit errors are detected:
+//! assert_eq!(ecc::decode(data ^ 0b11, check), Decoded::Uncorrectable);
+//! ```
+
+use crate::word::Narrow;
+use crate::Parity;
+use core::marker::PhantomData;
+
+/// Result of decoding a payload.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Decoded<T> {
+    /// The payload and the check byte have no errors.
+    Clean(T),
+    /// A single bit error in the payload or in the check byte was corrected.
+    Corrected(T),
+    /// Two or more bits have errors.
+    Uncorrectable,
+}
+
+/// Position in the Hamming codeword of the payload bit `j`: the positions
+/// that are powers of two are reserved for the check bits.
+const fn position(j: u32) -> u32 {
+    let mut p = 0;
+    let mut n = 0;
+    loop {
+        p += 1;
+        if p & (p - 1) != 0 {
+            if n == j {
+                return p;
+            }
+            n += 1;
+        }
+    }
+}
+
+/// Masks of the payload bits covered by each check bit.
+const fn masks(bits: u32) -> [u64; 7] {
+    let mut m = [0; 7];
+    let mut j = 0;
+    while j < bits {
+        let p = position(j);
+        let mut i = 0;
+        while i < 7 {
+            if p & (1 << i) != 0 {
+                m[i] |= 1 << j;
+            }
+            i += 1;
+        }
+        j += 1;
+    }
+    m
+}
+
+/// Number of Hamming check bits of a payload of `bits` bits: the smallest
+/// `r` such that the `2^r - 1` positions of the codeword fit the payload and
+/// the check bits.
+const fn check_bits(bits: u32) -> u32 {
+    let mut r = 0;
+    while (1 << r) < bits + r + 1 {
+        r += 1;
+    }
+    r
+}
+
+/// Parameters of the code of the payloads of type `T`, which are computed
+/// at compile-time.
+struct Code<T>(PhantomData<T>);
+
+impl<T: Narrow> Code<T> {
+    const CHECK_BITS: u32 = check_bits(T::BITS);
+    const MASKS: [u64; 7] = masks(T::BITS);
+}
+
+#[inline]
+fn parity(x: u64) -> u64 {
+    x.parity() as u64
+}
+
+/// Computes the Hamming check bits, without the overall parity bit.
+#[inline]
+fn hamming<T: Narrow>(data: u64) -> u8 {
+    let mut c = 0;
+    for i in 0..Code::<T>::CHECK_BITS {
+        c |= parity(data & Code::<T>::MASKS[i as usize]) << i;
+    }
+    c as u8
+}
+
+/// Computes the check byte of `data`.
+///
+/// # Example
+///
+/// ```
+/// # use bitintr::ecc;
+/// assert_eq!(ecc::encode(0_u32), 0);
+/// // Bit 0 of the payload is at position 3 == 0b11 of the codeword, and
+/// // the overall parity is 1:
+/// assert_eq!(ecc::encode(1_u32), 0b100_0011);
+/// ```
+#[inline]
+pub fn encode<T: Narrow>(data: T) -> u8 {
+    let data = data.to_u64();
+    let c = hamming::<T>(data);
+    let p = parity(data) ^ parity(u64::from(c));
+    c | (p << Code::<T>::CHECK_BITS) as u8
+}
+
+/// Computes the syndrome of `data` and `check`.
+///
+/// Returns a pair with the Hamming syndrome, which is zero if there are no
+/// errors and otherwise the position in the codeword of a single bit error,
+/// and with the overall parity, which is `true` if an odd number of bits
+/// have errors.
+///
+/// # Example
+///
+/// ```
+/// # use bitintr::ecc;
+/// let check = ecc::encode(0xAB_u8);
+/// assert_eq!(ecc::syndrome(0xAB_u8, check), (0, false));
+/// // Bit 0 of the payload is at position 3 of the codeword:
+/// assert_eq!(ecc::syndrome(0xAA_u8, check), (3, true));
+/// ```
+#[inline]
+pub fn syndrome<T: Narrow>(data: T, check: u8) -> (u8, bool) {
+    let data = data.to_u64();
+    let r = Code::<T>::CHECK_BITS;
+    let s = (hamming::<T>(data) ^ check) & ((1 << r) - 1);
+    let p = parity(data) ^ parity(u64::from(check) & ((2 << r) - 1));
+    (s, p != 0)
+}
+
+/// Decodes `data` protected by `check`, correcting single bit errors.
+///
+/// # Example
+///
+/// ```
+/// # use bitintr::ecc::{self, Decoded};
+/// let check = ecc::encode(0xBEEF_u16);
+/// assert_eq!(ecc::decode(0xBEEF_u16, check), Decoded::Clean(0xBEEF));
+/// assert_eq!(ecc::decode(0xBEEE_u16, check), Decoded::Corrected(0xBEEF));
+/// assert_eq!(ecc::decode(0xBEEC_u16, check), Decoded::Uncorrectable);
+/// ```
+#[inline]
+pub fn decode<T: Narrow>(data: T, check: u8) -> Decoded<T> {
+    let (s, p) = syndrome(data, check);
+    match (s, p) {
+        (0, false) => Decoded::Clean(data),
+        // Even number of errors:
+        (_, false) => Decoded::Uncorrectable,
+        // The error is in a check bit:
+        (s, true) if s & s.wrapping_sub(1) == 0 => Decoded::Corrected(data),
+        (s, true) => {
+            // Index of the payload bit at position `s`, skipping the
+            // positions of the check bits below it:
+            let j = u32::from(s) - (8 - s.leading_zeros()) - 1;
+            if j < T::BITS {
+                Decoded::Corrected(T::from_u64(data.to_u64() ^ (1 << j)))
+            } else {
+                Decoded::Uncorrectable
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{xorshift64, SEED};
+
+    /// Flips bit `i` of the codeword made of the payload followed by the
+    /// check byte.
+    fn flip<T: Narrow>(data: T, check: u8, i: u32) -> (T, u8) {
+        let bits = T::BITS;
+        if i < bits {
+            (T::from_u64(data.to_u64() ^ (1 << i)), check)
+        } else {
+            (data, check ^ (1 << (i - bits)))
+        }
+    }
+
+    fn check_errors<T: Narrow + PartialEq + core::fmt::Debug>(data: T) {
+        let check = encode(data);
+        let r = Code::<T>::CHECK_BITS;
+        let n = T::BITS + r + 1;
+        assert_eq!(u32::from(check) >> (r + 1), 0);
+        assert_eq!(decode(data, check), Decoded::Clean(data));
+        for i in 0..n {
+            let (d, c) = flip(data, check, i);
+            assert_eq!(decode(d, c), Decoded::Corrected(data), "bit {}", i);
+            for k in i + 1..n {
+                let (d, c) = flip(d, c, k);
+                assert_eq!(
+                    decode(d, c),
+                    Decoded::Uncorrectable,
+                    "{} {}",
+                    i,
+                    k
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn exhaustive_u8() {
+        for x in 0..=u8::MAX {
+            check_errors(x);
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let mut next = xorshift64(SEED);
+        for _ in 0..16 {
+            let x = next();
+            check_errors(x as u16);
+            check_errors(x as u32);
+            check_errors(x);
+        }
+        for &x in &[0, u64::MAX] {
+            check_errors(x as u16);
+            check_errors(x as u32);
+            check_errors(x);
+        }
+    }
+}
@@ -0,0 +1,117 @@
+//! fshl
+
+/// Funnel shift left
+pub trait Fshl {
+    /// Funnel shift left.
+    ///
+    /// Concatenates `self` (the high half) and `lo` (the low half) into a
+    /// value of twice the bit size, shifts it left by `n` bits, and returns
+    /// the high half of the result.
+    ///
+    /// Only `n % bit_size()` is used, so every `n` is valid. In particular,
+    /// shifting by a multiple of `bit_size()` returns `self`.
+    ///
+    /// **Keywords**: Funnel shift, double precision shift, extract.
+    ///
+    /// # Instructions
+    ///
+    /// - [`SHLD`](http://www.felixcloutier.com/x86/SHLD.html):
+    ///   - Description: Double precision shift left.
+    ///   - Architecture: x86.
+    ///   - Registers: 16/32/64 bit.
+    /// - [`EXTR`](http://infocenter.arm.com/help/index.jsp?topic=/com.arm.doc.
+    ///   ddi0487a.k_10775/index.html):
+    ///   - Description: Extract register (with `lsb == bit_size() - n`).
+    ///   - Architecture: ARMv8.
+    ///   - Registers: 32/64 bits.
+    /// - [`FSL`](https://github.com/riscv/riscv-bitmanip):
+    ///   - Description: Funnel shift left.
+    ///   - Architecture: RISC-V.
+    ///   - Instruction set: Zbt (draft).
+    ///   - Registers: 32/64 bit.
+    /// - Note: `FSL` takes the shift amount modulo `2 * bit_size()`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bitintr::*;
+    /// let hi = 0b1100_0011_u8;
+    /// let lo = 0b1010_0000_u8;
+    ///
+    /// assert_eq!(hi.fshl(lo, 0), hi);
+    /// assert_eq!(hi.fshl(lo, 3), 0b0001_1101_u8);
+    /// assert_eq!(hi.fshl(lo, 11), 0b0001_1101_u8);
+    /// assert_eq!(hi.fshl(hi, 3), hi.rotate_left(3));
+    /// ```
+    fn fshl(self, lo: Self, n: u32) -> Self;
+}
+
+macro_rules! impl_fshl {
+    ($id:ident, $uid:ident) => {
+        #[allow(clippy::use_self)]
+        impl Fshl for $id {
+            #[inline]
+            fn fshl(self, lo: Self, n: u32) -> Self {
+                const BIT_WIDTH: u32 =
+                    (crate::mem::size_of::<$id>() * 8) as u32;
+                let (hi, lo) = (self as $uid, lo as $uid);
+                let n = n % BIT_WIDTH;
+                // Shifting `lo` in two steps avoids overflowing the
+                // shift when `n == 0`.
+                ((hi << n) | ((lo >> 1) >> (BIT_WIDTH - 1 - n))) as Self
+            }
+        }
+    };
+}
+
+impl_fshl!(u8, u8);
+impl_fshl!(i8, u8);
+impl_fshl!(u16, u16);
+impl_fshl!(i16, u16);
+impl_fshl!(u32, u32);
+impl_fshl!(i32, u32);
+impl_fshl!(u64, u64);
+impl_fshl!(i64, u64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{xorshift64, SEED};
+
+    fn check(hi: u64, lo: u64, n: u32) {
+        let x = (u128::from(hi) << 64 | u128::from(lo)) << (n % 64);
+        let r = (x >> 64) as u64;
+        assert_eq!(hi.fshl(lo, n), r, "{} {} {}", hi, lo, n);
+        assert_eq!((hi as i64).fshl(lo as i64, n), r as i64);
+        let (hi, lo) = (hi as u32, lo as u32);
+        let x = (u64::from(hi) << 32 | u64::from(lo)) << (n % 32);
+        let r = (x >> 32) as u32;
+        assert_eq!(hi.fshl(lo, n), r, "{} {} {}", hi, lo, n);
+        assert_eq!((hi as i32).fshl(lo as i32, n), r as i32);
+        let (hi, lo) = (hi as u16, lo as u16);
+        let x = (u32::from(hi) << 16 | u32::from(lo)) << (n % 16);
+        let r = (x >> 16) as u16;
+        assert_eq!(hi.fshl(lo, n), r, "{} {} {}", hi, lo, n);
+        assert_eq!((hi as i16).fshl(lo as i16, n), r as i16);
+    }
+
+    #[test]
+    fn fshl() {
+        for hi in 0..=u8::MAX {
+            for lo in 0..=u8::MAX {
+                for n in 0..=16 {
+                    let x = (u16::from(hi) << 8 | u16::from(lo)) << (n % 8);
+                    let r = (x >> 8) as u8;
+                    assert_eq!(hi.fshl(lo, n), r, "{} {} {}", hi, lo, n);
+                    assert_eq!((hi as i8).fshl(lo as i8, n), r as i8);
+                }
+            }
+        }
+        let mut next = xorshift64(SEED);
+        for _ in 0..10_000 {
+            let (hi, lo, n) = (next(), next(), next());
+            check(hi, lo, n as u32);
+            check(hi, lo, n as u32 % 64);
+        }
+    }
+}
@@ -0,0 +1,117 @@
+//! fshr
+
+/// Funnel shift right
+pub trait Fshr {
+    /// Funnel shift right.
+    ///
+    /// Concatenates `self` (the high half) and `lo` (the low half) into a
+    /// value of twice the bit size, shifts it right by `n` bits, and returns
+    /// the low half of the result.
+    ///
+    /// Only `n % bit_size()` is used, so every `n` is valid. In particular,
+    /// shifting by a multiple of `bit_size()` returns `lo`.
+    ///
+    /// **Keywords**: Funnel shift, double precision shift, extract.
+    ///
+    /// # Instructions
+    ///
+    /// - [`SHRD`](http://www.felixcloutier.com/x86/SHRD.html):
+    ///   - Description: Double precision shift right.
+    ///   - Architecture: x86.
+    ///   - Registers: 16/32/64 bit.
+    /// - [`EXTR`](http://infocenter.arm.com/help/index.jsp?topic=/com.arm.doc.
+    ///   ddi0487a.k_10775/index.html):
+    ///   - Description: Extract register.
+    ///   - Architecture: ARMv8.
+    ///   - Registers: 32/64 bits.
+    /// - [`FSR`](https://github.com/riscv/riscv-bitmanip):
+    ///   - Description: Funnel shift right.
+    ///   - Architecture: RISC-V.
+    ///   - Instruction set: Zbt (draft).
+    ///   - Registers: 32/64 bit.
+    /// - Note: `FSR` takes the shift amount modulo `2 * bit_size()`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bitintr::*;
+    /// let hi = 0b1100_0011_u8;
+    /// let lo = 0b1010_0000_u8;
+    ///
+    /// assert_eq!(hi.fshr(lo, 0), lo);
+    /// assert_eq!(hi.fshr(lo, 3), 0b0111_0100_u8);
+    /// assert_eq!(hi.fshr(lo, 11), 0b0111_0100_u8);
+    /// assert_eq!(hi.fshr(hi, 3), hi.rotate_right(3));
+    /// ```
+    fn fshr(self, lo: Self, n: u32) -> Self;
+}
+
+macro_rules! impl_fshr {
+    ($id:ident, $uid:ident) => {
+        #[allow(clippy::use_self)]
+        impl Fshr for $id {
+            #[inline]
+            fn fshr(self, lo: Self, n: u32) -> Self {
+                const BIT_WIDTH: u32 =
+                    (crate::mem::size_of::<$id>() * 8) as u32;
+                let (hi, lo) = (self as $uid, lo as $uid);
+                let n = n % BIT_WIDTH;
+                // Shifting `hi` in two steps avoids overflowing the
+                // shift when `n == 0`.
+                (((hi << 1) << (BIT_WIDTH - 1 - n)) | (lo >> n)) as Self
+            }
+        }
+    };
+}
+
+impl_fshr!(u8, u8);
+impl_fshr!(i8, u8);
+impl_fshr!(u16, u16);
+impl_fshr!(i16, u16);
+impl_fshr!(u32, u32);
+impl_fshr!(i32, u32);
+impl_fshr!(u64, u64);
+impl_fshr!(i64, u64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{xorshift64, SEED};
+
+    fn check(hi: u64, lo: u64, n: u32) {
+        let x = (u128::from(hi) << 64 | u128::from(lo)) >> (n % 64);
+        let r = x as u64;
+        assert_eq!(hi.fshr(lo, n), r, "{} {} {}", hi, lo, n);
+        assert_eq!((hi as i64).fshr(lo as i64, n), r as i64);
+        let (hi, lo) = (hi as u32, lo as u32);
+        let x = (u64::from(hi) << 32 | u64::from(lo)) >> (n % 32);
+        let r = x as u32;
+        assert_eq!(hi.fshr(lo, n), r, "{} {} {}", hi, lo, n);
+        assert_eq!((hi as i32).fshr(lo as i32, n), r as i32);
+        let (hi, lo) = (hi as u16, lo as u16);
+        let x = (u32::from(hi) << 16 | u32::from(lo)) >> (n % 16);
+        let r = x as u16;
+        assert_eq!(hi.fshr(lo, n), r, "{} {} {}", hi, lo, n);
+        assert_eq!((hi as i16).fshr(lo as i16, n), r as i16);
+    }
+
+    #[test]
+    fn fshr() {
+        for hi in 0..=u8::MAX {
+            for lo in 0..=u8::MAX {
+                for n in 0..=16 {
+                    let x = (u16::from(hi) << 8 | u16::from(lo)) >> (n % 8);
+                    let r = x as u8;
+                    assert_eq!(hi.fshr(lo, n), r, "{} {} {}", hi, lo, n);
+                    assert_eq!((hi as i8).fshr(lo as i8, n), r as i8);
+                }
+            }
+        }
+        let mut next = xorshift64(SEED);
+        for _ in 0..10_000 {
+            let (hi, lo, n) = (next(), next(), next());
+            check(hi, lo, n as u32);
+            check(hi, lo, n as u32 % 64);
+        }
+    }
+}
@@ -0,0 +1,318 @@
+//! Polynomial arithmetic over GF(2).
+//!
+//! An unsigned integer of `W` bits, one of `u8`, `u16`, `u32`, `u64` and
+//! `u128`, represents a polynomial over GF(2) of degree smaller than `W`:
+//! bit `i` is the coefficient of `x^i`.
+//!
+//! The `_mod` functions compute in the field GF(2^W) defined by the
+//! irreducible polynomial `x^W + poly`, where only the low part `poly` is
+//! passed, since `x^W` does not fit in `W` bits. For example:
+//!
+//! - GF(2^8) of AES: `poly == 0x1B`,
+//! - GF(2^8) of Reed-Solomon codes like QR codes: `poly == 0x1D`,
+//! - GF(2^64): `poly == 0x1B`,
+//! - GF(2^128) of GHASH (in the non-reflected bit order): `poly == 0x87`.
+//!
+//! Products are computed with [`Clmul`](../trait.Clmul.html), which uses
+//! carry-less multiply instructions if they are available and a portable
+//! implementation otherwise.
+
+use crate::word::Word;
+
+/// Multiplies the polynomials `a` and `b`.
+///
+/// Returns a pair `(lo, hi)` with the low and the high half of the product.
+///
+/// # Example
+///
+/// ```
+/// # use bitintr::gf2;
+/// // (x + 1) * (x^2 + x + 1) == x^3 + 1
+/// assert_eq!(gf2::mul(0b11_u8, 0b111), (0b1001, 0));
+/// assert_eq!(gf2::mul(1_u128 << 127, 0b10), (0, 1));
+/// ```
+#[inline]
+pub fn mul<T: Word>(a: T, b: T) -> (T, T) {
+    a.clmul_(b)
+}
+
+/// Degree of the polynomial `a`, or `None` if `a == 0`.
+///
+/// # Example
+///
+/// ```
+/// # use bitintr::gf2;
+/// assert_eq!(gf2::degree(0_u32), None);
+/// assert_eq!(gf2::degree(1_u32), Some(0));
+/// assert_eq!(gf2::degree(0b1011_u32), Some(3));
+/// assert_eq!(gf2::degree(u128::MAX), Some(127));
+/// ```
+#[inline]
+pub fn degree<T: Word>(a: T) -> Option<u32> {
+    if a == T::ZERO {
+        None
+    } else {
+        Some(T::BITS - 1 - a.lzcnt_())
+    }
+}
+
+/// Remainder of the division of the polynomial `a` by `m`.
+///
+/// # Panics
+///
+/// If `m == 0`.
+///
+/// # Example
+///
+/// ```
+/// # use bitintr::gf2;
+/// // x^3 + 1 == (x + 1) * (x^2 + x + 1)
+/// assert_eq!(gf2::rem(0b1001_u8, 0b111), 0);
+/// assert_eq!(gf2::rem(0b1000_u8, 0b111), 1);
+/// ```
+#[inline]
+pub fn rem<T: Word>(mut a: T, m: T) -> T {
+    let dm = degree(m).expect("attempt to divide by the zero polynomial");
+    while let Some(da) = degree(a) {
+        if da < dm {
+            break;
+        }
+        a = a.xor(m.shl(da - dm));
+    }
+    a
+}
+
+/// Reduces the polynomial `hi * x^W + lo` modulo `x^W + poly`.
+///
+/// # Example
+///
+/// ```
+/// # use bitintr::gf2;
+/// // x^8 == x^4 + x^3 + x + 1 (mod x^8 + x^4 + x^3 + x + 1)
+/// assert_eq!(gf2::reduce(0_u8, 1, 0x1B), 0x1B);
+/// let (lo, hi) = gf2::mul(0x57_u8, 0x83);
+/// assert_eq!(gf2::reduce(lo, hi, 0x1B), 0xC1);
+/// ```
+#[inline]
+pub fn reduce<T: Word>(mut lo: T, mut hi: T, poly: T) -> T {
+    // Since `x^W == poly`, `hi * x^W == hi * poly`, whose high half has a
+    // smaller degree than `hi`.
+    while hi != T::ZERO {
+        let (l, h) = hi.clmul_(poly);
+        lo = lo.xor(l);
+        hi = h;
+    }
+    lo
+}
+
+/// Multiplies `a` and `b` in GF(2^W) modulo `x^W + poly`.
+///
+/// # Example
+///
+/// ```
+/// # use bitintr::*;
+/// assert_eq!(gf2::mul_mod(0x57_u8, 0x83, 0x1B), 0x57_u8.gf2p8mul(0x83));
+/// ```
+#[inline]
+pub fn mul_mod<T: Word>(a: T, b: T, poly: T) -> T {
+    let (lo, hi) = a.clmul_(b);
+    reduce(lo, hi, poly)
+}
+
+/// Raises `a` to the power `e` in GF(2^W) modulo `x^W + poly`.
+///
+/// # Example
+///
+/// ```
+/// # use bitintr::gf2;
+/// // 3 generates the multiplicative group of the AES field:
+/// assert_eq!(gf2::pow_mod(3_u8, 255, 0x1B), 1);
+/// assert_ne!(gf2::pow_mod(3_u8, 85, 0x1B), 1);
+/// assert_eq!(gf2::pow_mod(0_u8, 0, 0x1B), 1);
+/// ```
+#[inline]
+pub fn pow_mod<T: Word>(mut a: T, mut e: u128, poly: T) -> T {
+    let mut r = T::ONE;
+    while e != 0 {
+        if e & 1 != 0 {
+            r = mul_mod(r, a, poly);
+        }
+        a = mul_mod(a, a, poly);
+        e >>= 1;
+    }
+    r
+}
+
+/// Multiplicative inverse of `a` modulo `x^W + poly`.
+///
+/// Returns `None` if `a` has no inverse, which for an irreducible
+/// `x^W + poly` only happens if `a == 0`.
+///
+/// The inverse is computed with the extended Euclidean algorithm.
+///
+/// # Example
+///
+/// ```
+/// # use bitintr::gf2;
+/// assert_eq!(gf2::inv_mod(0x53_u8, 0x1B), Some(0xCA));
+/// assert_eq!(gf2::inv_mod(0_u8, 0x1B), None);
+/// // x^8 + 1 == (x + 1)^8 is not irreducible:
+/// assert_eq!(gf2::inv_mod(0b11_u8, 0x01), None);
+///
+/// let a = 0x0123_4567_89AB_CDEF_0123_4567_89AB_CDEF_u128;
+/// let inv = gf2::inv_mod(a, 0x87).unwrap();
+/// assert_eq!(gf2::mul_mod(a, inv, 0x87), 1);
+/// ```
+#[inline]
+pub fn inv_mod<T: Word>(a: T, poly: T) -> Option<T> {
+    let d = degree(a)?;
+    if d == 0 {
+        return Some(T::ONE);
+    }
+    // First division step of `x^W + poly` by `a`, whose leading term
+    // cancels `x^W`:
+    let mut r0 = a;
+    let mut r1 = poly.xor(a.shl(T::BITS - d));
+    let mut q = T::ONE.shl(T::BITS - d);
+    while let Some(d1) = degree(r1) {
+        if d1 < d {
+            break;
+        }
+        r1 = r1.xor(a.shl(d1 - d));
+        q = q.xor(T::ONE.shl(d1 - d));
+    }
+    // Invariant: `ti * a == ri (mod x^W + poly)`.
+    let mut t0 = T::ONE;
+    let mut t1 = q;
+    while r1 != T::ZERO {
+        let (mut q, mut r) = (T::ZERO, r0);
+        let d1 = degree(r1).unwrap();
+        while let Some(dr) = degree(r) {
+            if dr < d1 {
+                break;
+            }
+            r = r.xor(r1.shl(dr - d1));
+            q = q.xor(T::ONE.shl(dr - d1));
+        }
+        let t = t0.xor(q.clmul_(t1).0);
+        t0 = t1;
+        t1 = t;
+        r0 = r1;
+        r1 = r;
+    }
+    if r0 == T::ONE {
+        Some(t0)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{xorshift64, SEED};
+    use crate::Gf2p8Mul;
+
+    /// Bit-serial multiplication modulo `x^W + poly`.
+    fn mul_mod_naive<T: Word>(a: T, b: T, poly: T) -> T {
+        let mut r = T::ZERO;
+        for i in (0..T::BITS).rev() {
+            let carry = r.bit(T::BITS - 1);
+            r = r.shl(1);
+            if carry {
+                r = r.xor(poly);
+            }
+            if b.bit(i) {
+                r = r.xor(a);
+            }
+        }
+        r
+    }
+
+    /// Bit-serial carry-less multiplication.
+    fn mul_naive<T: Word>(a: T, b: T) -> (T, T) {
+        let (mut lo, mut hi) = (T::ZERO, T::ZERO);
+        for i in 0..T::BITS {
+            if b.bit(i) {
+                lo = lo.xor(a.shl(i));
+                if i != 0 {
+                    for j in T::BITS - i..T::BITS {
+                        if a.bit(j) {
+                            hi = hi.xor(T::ONE.shl(j + i - T::BITS));
+                        }
+                    }
+                }
+            }
+        }
+        (lo, hi)
+    }
+
+    #[test]
+    fn gf2_8() {
+        for a in 0..=u8::MAX {
+            for b in 0..=u8::MAX {
+                assert_eq!(mul(a, b), mul_naive(a, b));
+                let p = mul_mod(a, b, 0x1B);
+                assert_eq!(p, a.gf2p8mul(b));
+                assert_eq!(mul_mod(a, b, 0x1D), mul_mod_naive(a, b, 0x1D));
+            }
+            if a != 0 {
+                for &poly in &[0x1B, 0x1D] {
+                    let inv = inv_mod(a, poly).unwrap();
+                    assert_eq!(mul_mod(a, inv, poly), 1);
+                    assert_eq!(pow_mod(a, 254, poly), inv);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn gf2_16() {
+        // x^16 + x^5 + x^3 + x + 1
+        const POLY: u16 = 0x2B;
+        for a in 1..=u16::MAX {
+            let inv = inv_mod(a, POLY).unwrap();
+            assert_eq!(mul_mod(a, inv, POLY), 1);
+            assert_eq!(mul_mod_naive(a, inv, POLY), 1);
+        }
+    }
+
+    macro_rules! wide {
+        ($name:ident, $id:ident, $poly:expr) => {
+            #[test]
+            fn $name() {
+                let mut rng = xorshift64(SEED);
+                let mut next = || {
+                    let x = rng();
+                    ((u128::from(x) << 64) | u128::from(x.rotate_left(17)))
+                        as $id
+                };
+                for _ in 0..256 {
+                    let (a, b) = (next(), next());
+                    assert_eq!(mul(a, b), mul_naive(a, b));
+                    assert_eq!(
+                        mul_mod(a, b, $poly),
+                        mul_mod_naive(a, b, $poly)
+                    );
+                    let inv = inv_mod(a, $poly).unwrap();
+                    assert_eq!(mul_mod_naive(a, inv, $poly), 1);
+                    let e = u128::MAX >> (128 - <$id as Word>::BITS);
+                    assert_eq!(pow_mod(a, e - 1, $poly), inv);
+                    // Dense moduli are reduced in several steps:
+                    let dense = !0 >> 1;
+                    assert_eq!(
+                        mul_mod(a, b, dense),
+                        mul_mod_naive(a, b, dense)
+                    );
+                }
+            }
+        };
+    }
+
+    // x^32 + x^7 + x^3 + x^2 + 1
+    wide!(gf2_32, u32, 0x8D);
+    // x^64 + x^4 + x^3 + x + 1
+    wide!(gf2_64, u64, 0x1B);
+    // x^128 + x^7 + x^2 + x + 1
+    wide!(gf2_128, u128, 0x87);
+}
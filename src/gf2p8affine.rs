@@ -0,0 +1,236 @@
+//! gf2p8affine
+
+/// Galois Field affine transformation
+pub trait Gf2p8Affine {
+    /// Galois Field affine transformation.
+    ///
+    /// Computes the affine transformation `A * x + b` of each byte `x` of
+    /// `self`, where `A` is the 8x8 bit `matrix` and `b` is an 8-bit vector.
+    ///
+    /// Bit `i` of each result byte is the parity of `x` AND-ed with byte
+    /// `7 - i` of `matrix`, XOR-ed with bit `i` of `b`. This can express any
+    /// linear map of the bits of a byte, for example:
+    ///
+    /// - `0x0102_0408_1020_4080`: identity,
+    /// - `0x8040_2010_0804_0201`: bit reversal (see [`Rbit`](trait.Rbit.html)),
+    /// - `0x0000_0000_0000_0000`: constant `b`.
+    ///
+    /// It is implemented for `u8` and for `u64`, which is treated as eight
+    /// independent bytes that are all transformed with the same `matrix`.
+    ///
+    /// # Instructions
+    ///
+    /// - [`GF2P8AFFINEQB`](http://www.felixcloutier.com/x86/GF2P8AFFINEQB.html):
+    ///   - Description: Galois Field affine transformation.
+    ///   - Architecture: x86.
+    ///   - Instruction set: GFNI.
+    ///   - Registers: 128/256/512 bit.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bitintr::*;
+    /// const IDENTITY: u64 = 0x0102_0408_1020_4080;
+    /// const RBIT: u64 = 0x8040_2010_0804_0201;
+    ///
+    /// let n = 0b0011_0001_u8;
+    /// assert_eq!(n.gf2p8affine(IDENTITY, 0), n);
+    /// assert_eq!(n.gf2p8affine(IDENTITY, 0xFF), !n);
+    /// assert_eq!(n.gf2p8affine(RBIT, 0), n.rbit());
+    /// assert_eq!(
+    ///     0x0123_4567_89AB_CDEFu64.gf2p8affine(RBIT, 0),
+    ///     0x0123_4567_89AB_CDEFu64.rbit().rev()
+    /// );
+    /// ```
+    fn gf2p8affine(self, matrix: u64, b: u8) -> Self;
+
+    /// Galois Field affine transformation of the inverse.
+    ///
+    /// Like [`gf2p8affine`](#tymethod.gf2p8affine), but transforms the
+    /// multiplicative inverse of each byte in GF(2^8) (see
+    /// [`Gf2p8Mul`](trait.Gf2p8Mul.html)). The inverse of `0` is `0`.
+    ///
+    /// With the matrix `0xF1E3_C78F_1F3E_7CF8` and `b == 0x63` this computes
+    /// the AES S-box.
+    ///
+    /// # Instructions
+    ///
+    /// - [`GF2P8AFFINEINVQB`](http://www.felixcloutier.com/x86/GF2P8AFFINEINVQB.html):
+    ///   - Description: Galois Field affine transformation inverse.
+    ///   - Architecture: x86.
+    ///   - Instruction set: GFNI.
+    ///   - Registers: 128/256/512 bit.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bitintr::*;
+    /// const IDENTITY: u64 = 0x0102_0408_1020_4080;
+    /// const AES: u64 = 0xF1E3_C78F_1F3E_7CF8;
+    ///
+    /// assert_eq!(0x53u8.gf2p8affineinv(IDENTITY, 0), 0xCA);
+    /// assert_eq!(0x00u8.gf2p8affineinv(AES, 0x63), 0x63);
+    /// assert_eq!(0x53u8.gf2p8affineinv(AES, 0x63), 0xED);
+    /// ```
+    fn gf2p8affineinv(self, matrix: u64, b: u8) -> Self;
+}
+
+/// Broadcasts a byte to the eight bytes of a `u64`.
+#[inline]
+pub(crate) fn splat(b: u8) -> u64 {
+    u64::from(b) * 0x0101_0101_0101_0101
+}
+
+cfg_if! {
+    if #[cfg(all(target_arch = "x86_64", target_feature = "gfni"))] {
+        #[inline]
+        #[target_feature(enable = "gfni")]
+        unsafe fn gf2p8affine_(x: u64, matrix: u64) -> u64 {
+            use crate::arch::*;
+            let r = _mm_gf2p8affine_epi64_epi8(
+                _mm_cvtsi64_si128(x as i64),
+                _mm_cvtsi64_si128(matrix as i64),
+                0,
+            );
+            _mm_cvtsi128_si64(r) as u64
+        }
+
+        #[inline]
+        #[target_feature(enable = "gfni")]
+        unsafe fn gf2p8affineinv_(x: u64, matrix: u64) -> u64 {
+            use crate::arch::*;
+            let r = _mm_gf2p8affineinv_epi64_epi8(
+                _mm_cvtsi64_si128(x as i64),
+                _mm_cvtsi64_si128(matrix as i64),
+                0,
+            );
+            _mm_cvtsi128_si64(r) as u64
+        }
+    } else {
+        #[inline]
+        fn gf2p8affine_(x: u64, matrix: u64) -> u64 {
+            const LSB: u64 = 0x0101_0101_0101_0101;
+            let mut r = 0;
+            for i in 0..8 {
+                let row = (matrix >> (8 * (7 - i))) as u8;
+                // Parity of each byte, in the least significant bit:
+                let mut t = x & splat(row);
+                t ^= t >> 4;
+                t ^= t >> 2;
+                t ^= t >> 1;
+                r |= (t & LSB) << i;
+            }
+            r
+        }
+
+        #[inline]
+        fn gf2p8affineinv_(x: u64, matrix: u64) -> u64 {
+            use crate::Gf2p8Mul;
+            // x^-1 == x^254 in GF(2^8), and 0^254 == 0.
+            let x2 = x.gf2p8mul(x);
+            let x3 = x2.gf2p8mul(x);
+            let x6 = x3.gf2p8mul(x3);
+            let x12 = x6.gf2p8mul(x6);
+            let x14 = x12.gf2p8mul(x2);
+            let mut x240 = x12.gf2p8mul(x3);
+            for _ in 0..4 {
+                x240 = x240.gf2p8mul(x240);
+            }
+            gf2p8affine_(x240.gf2p8mul(x14), matrix)
+        }
+    }
+}
+
+impl Gf2p8Affine for u8 {
+    #[inline]
+    #[allow(unused_unsafe)]
+    fn gf2p8affine(self, matrix: u64, b: u8) -> Self {
+        // UNSAFETY: this is always safe, because
+        // the unsafe `#[target_feature]` function
+        // is only generated when the feature is
+        // statically-enabled at compile-time.
+        unsafe { gf2p8affine_(u64::from(self), matrix) as u8 ^ b }
+    }
+
+    #[inline]
+    #[allow(unused_unsafe)]
+    fn gf2p8affineinv(self, matrix: u64, b: u8) -> Self {
+        // UNSAFETY: see above.
+        unsafe { gf2p8affineinv_(u64::from(self), matrix) as u8 ^ b }
+    }
+}
+
+impl Gf2p8Affine for u64 {
+    #[inline]
+    #[allow(unused_unsafe)]
+    fn gf2p8affine(self, matrix: u64, b: u8) -> Self {
+        // UNSAFETY: see above.
+        unsafe { gf2p8affine_(self, matrix) ^ splat(b) }
+    }
+
+    #[inline]
+    #[allow(unused_unsafe)]
+    fn gf2p8affineinv(self, matrix: u64, b: u8) -> Self {
+        // UNSAFETY: see above.
+        unsafe { gf2p8affineinv_(self, matrix) ^ splat(b) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_util::{xorshift64, SEED};
+    use crate::{Gf2p8Affine, Gf2p8Mul};
+
+    const IDENTITY: u64 = 0x0102_0408_1020_4080;
+
+    /// Bit `i` of the result is the parity of `x` AND-ed with row `7 - i` of
+    /// `matrix`, XOR-ed with bit `i` of `b`.
+    fn model(x: u8, matrix: u64, b: u8) -> u8 {
+        (0..8).fold(b, |r, i| {
+            let row = (matrix >> (8 * (7 - i))) as u8;
+            r ^ (((row & x).count_ones() & 1) << i) as u8
+        })
+    }
+
+    #[test]
+    fn gf2p8affine_u8() {
+        let mut next = xorshift64(SEED);
+        for x in 0..=u8::MAX {
+            assert_eq!(x.gf2p8affine(IDENTITY, 0), x);
+            assert_eq!(x.gf2p8affine(0, 0xA5), 0xA5);
+            for _ in 0..256 {
+                let (matrix, b) = (next(), next() as u8);
+                assert_eq!(
+                    x.gf2p8affine(matrix, b),
+                    model(x, matrix, b),
+                    "{} {} {}",
+                    x,
+                    matrix,
+                    b
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn gf2p8affine_u64() {
+        let mut next = xorshift64(SEED);
+        for _ in 0..10_000 {
+            let (x, matrix, b) = (next(), next(), next() as u8);
+            assert_eq!(x.gf2p8affine(IDENTITY, 0), x);
+            let r = x.gf2p8affine(matrix, b);
+            for i in 0..8 {
+                let byte = (x >> (8 * i)) as u8;
+                assert_eq!((r >> (8 * i)) as u8, model(byte, matrix, b));
+            }
+        }
+    }
+
+    #[test]
+    fn gf2p8affineinv_u8() {
+        assert_eq!(0u8.gf2p8affineinv(IDENTITY, 0), 0);
+        for a in 1..=u8::MAX {
+            assert_eq!(a.gf2p8mul(a.gf2p8affineinv(IDENTITY, 0)), 1);
+        }
+    }
+}
@@ -0,0 +1,121 @@
+//! gf2p8mul
+
+/// Galois Field multiply bytes
+pub trait Gf2p8Mul {
+    /// Galois Field multiply bytes.
+    ///
+    /// Multiplies each byte of `self` with the corresponding byte of `y` in
+    /// the finite field GF(2^8), modulo the reduction polynomial
+    /// `x^8 + x^4 + x^3 + x + 1` (the one used by AES).
+    ///
+    /// It is implemented for `u8` and for `u64`, which is treated as eight
+    /// independent bytes.
+    ///
+    /// # Instructions
+    ///
+    /// - [`GF2P8MULB`](http://www.felixcloutier.com/x86/GF2P8MULB.html):
+    ///   - Description: Galois Field multiply bytes.
+    ///   - Architecture: x86.
+    ///   - Instruction set: GFNI.
+    ///   - Registers: 128/256/512 bit.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bitintr::*;
+    /// assert_eq!(0x53u8.gf2p8mul(0xCA), 0x01);
+    /// assert_eq!(0x57u8.gf2p8mul(0x83), 0xC1);
+    /// assert_eq!(
+    ///     0x0000_0000_0000_5357u64.gf2p8mul(0x0000_0000_0000_CA83),
+    ///     0x0000_0000_0000_01C1
+    /// );
+    /// ```
+    fn gf2p8mul(self, y: Self) -> Self;
+}
+
+cfg_if! {
+    if #[cfg(all(target_arch = "x86_64", target_feature = "gfni"))] {
+        #[inline]
+        #[target_feature(enable = "gfni")]
+        unsafe fn gf2p8mul_(x: u64, y: u64) -> u64 {
+            use crate::arch::*;
+            let r = _mm_gf2p8mul_epi8(
+                _mm_cvtsi64_si128(x as i64),
+                _mm_cvtsi64_si128(y as i64),
+            );
+            _mm_cvtsi128_si64(r) as u64
+        }
+    } else {
+        #[inline]
+        fn gf2p8mul_(mut x: u64, mut y: u64) -> u64 {
+            const LSB: u64 = 0x0101_0101_0101_0101;
+            let mut r = 0;
+            for _ in 0..8 {
+                r ^= x & ((y & LSB) * 0xFF);
+                let carry = (x >> 7) & LSB;
+                x = ((x << 1) & !LSB) ^ (carry * 0x1B);
+                y = (y >> 1) & !(LSB << 7);
+            }
+            r
+        }
+    }
+}
+
+impl Gf2p8Mul for u8 {
+    #[inline]
+    #[allow(unused_unsafe)]
+    fn gf2p8mul(self, y: Self) -> Self {
+        // UNSAFETY: this is always safe, because
+        // the unsafe `#[target_feature]` function
+        // is only generated when the feature is
+        // statically-enabled at compile-time.
+        unsafe { gf2p8mul_(u64::from(self), u64::from(y)) as u8 }
+    }
+}
+
+impl Gf2p8Mul for u64 {
+    #[inline]
+    #[allow(unused_unsafe)]
+    fn gf2p8mul(self, y: Self) -> Self {
+        // UNSAFETY: see above.
+        unsafe { gf2p8mul_(self, y) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Gf2p8Mul;
+
+    fn gf2p8mul_naive(mut a: u8, mut b: u8) -> u8 {
+        let mut r = 0;
+        while b != 0 {
+            if b & 1 != 0 {
+                r ^= a;
+            }
+            a = (a << 1) ^ if a & 0x80 != 0 { 0x1B } else { 0 };
+            b >>= 1;
+        }
+        r
+    }
+
+    #[test]
+    fn gf2p8mul_u8() {
+        for a in 0..=u8::MAX {
+            for b in 0..=u8::MAX {
+                assert_eq!(a.gf2p8mul(b), gf2p8mul_naive(a, b));
+            }
+        }
+    }
+
+    #[test]
+    fn gf2p8mul_u64() {
+        let x = 0x0123_4567_89AB_CDEF_u64;
+        let y = 0xFEDC_BA98_7654_3210_u64;
+        let r = x.gf2p8mul(y);
+        for i in 0..8 {
+            let s = 8 * i;
+            let (a, b) = ((x >> s) as u8, (y >> s) as u8);
+            assert_eq!((r >> s) as u8, gf2p8mul_naive(a, b));
+        }
+    }
+}
@@ -0,0 +1,107 @@
+//! gorc
+
+use crate::grev::STAGE_MASKS;
+
+/// Generalized OR-combine
+pub trait Gorc {
+    /// Generalized OR-combine.
+    ///
+    /// Like [`Grev`](trait.Grev.html), but for each bit `i` set in the
+    /// control word `k` the swapped value is OR-ed into the result instead of
+    /// replacing it. Only the low `log2(bit_size())` bits of `k` are used.
+    ///
+    /// Each bit of the result is the OR of all the bits of `self` whose index
+    /// can be reached by XOR-ing it with a subset of `k`. For example, with
+    /// `k == 7` every byte of the result is `0xFF` if the corresponding byte
+    /// of `self` is non-zero, and `0` otherwise.
+    ///
+    /// **Keywords**: Generalized OR-combine, OR-combine bytes.
+    ///
+    /// # Instructions
+    ///
+    /// - [`GORC`](https://github.com/riscv/riscv-bitmanip):
+    ///   - Description: Generalized OR-combine.
+    ///   - Architecture: RISC-V.
+    ///   - Instruction set: Zbp (draft).
+    ///   - Registers: 32/64 bit.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bitintr::*;
+    /// let n = 0b0000_0000_0100_0010u16;
+    ///
+    /// assert_eq!(n.gorc(1), 0b0000_0000_1100_0011u16);
+    /// assert_eq!(n.gorc(7), 0b0000_0000_1111_1111u16);
+    /// assert_eq!(n.gorc(15), 0b1111_1111_1111_1111u16);
+    /// assert_eq!(0u16.gorc(15), 0u16);
+    /// ```
+    fn gorc(self, k: u32) -> Self;
+}
+
+macro_rules! impl_gorc {
+    ($id:ident, $uid:ident) => {
+        #[allow(clippy::use_self)]
+        impl Gorc for $id {
+            #[inline]
+            fn gorc(self, k: u32) -> Self {
+                const BIT_WIDTH: u32 =
+                    (crate::mem::size_of::<$id>() * 8) as u32;
+                let k = k & (BIT_WIDTH - 1);
+                let mut x = self as $uid;
+                for (i, &m) in STAGE_MASKS.iter().enumerate() {
+                    let s = 1 << i;
+                    if k & s != 0 {
+                        let m = m as $uid;
+                        x |= ((x & m) << s) | ((x & !m) >> s);
+                    }
+                }
+                x as Self
+            }
+        }
+    };
+}
+
+impl_gorc!(u8, u8);
+impl_gorc!(i8, u8);
+impl_gorc!(u16, u16);
+impl_gorc!(i16, u16);
+impl_gorc!(u32, u32);
+impl_gorc!(i32, u32);
+impl_gorc!(u64, u64);
+impl_gorc!(i64, u64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Grev;
+
+    #[test]
+    fn gorc_u16() {
+        for k in 0..16 {
+            // Bit `i` is the OR of the bits `i ^ j` for the subsets `j` of
+            // `k`, that is, of the bits selected by `masks[i]`:
+            let mut masks = [0_u16; 16];
+            for (i, m) in masks.iter_mut().enumerate() {
+                for j in (0..16).filter(|&j| j & k == j) {
+                    *m |= 1 << (i ^ j);
+                }
+            }
+            for x in 0..=u16::MAX {
+                let mut expected = 0_u16;
+                for (i, &m) in masks.iter().enumerate() {
+                    if x & m != 0 {
+                        expected |= 1 << i;
+                    }
+                }
+                let y = x.gorc(k as u32);
+                assert_eq!(y, expected, "{:#x} {}", x, k);
+                assert_eq!((x as i16).gorc(k as u32), expected as i16);
+                // The OR-combine is idempotent and invariant under the
+                // generalized reverse with the same control word:
+                assert_eq!(y.gorc(k as u32), y);
+                assert_eq!(y.grev(k as u32), y);
+            }
+        }
+    }
+}
@@ -0,0 +1,142 @@
+//! grev
+
+/// Masks selecting the low half of each group swapped by the stages of the
+/// generalized reverse: groups of 1, 2, 4, 8, 16 and 32 bits.
+pub(crate) const STAGE_MASKS: [u64; 6] = [
+    0x5555_5555_5555_5555,
+    0x3333_3333_3333_3333,
+    0x0F0F_0F0F_0F0F_0F0F,
+    0x00FF_00FF_00FF_00FF,
+    0x0000_FFFF_0000_FFFF,
+    0x0000_0000_FFFF_FFFF,
+];
+
+/// Generalized reverse of the zero-extended `x` with the stages selected by
+/// `k`, which must be smaller than the bit width of `x` before the
+/// extension, so that the groups swapped never cross it.
+#[inline]
+fn grev_stages(mut x: u64, k: u32) -> u64 {
+    for (i, &m) in STAGE_MASKS.iter().enumerate() {
+        let s = 1 << i;
+        if k & s != 0 {
+            x = ((x & m) << s) | ((x & !m) >> s);
+        }
+    }
+    x
+}
+
+/// Generalized reverse
+pub trait Grev {
+    /// Generalized reverse.
+    ///
+    /// For each bit `i` set in the control word `k`, swaps the adjacent
+    /// groups of `1 << i` bits of `self`. Only the low `log2(bit_size())`
+    /// bits of `k` are used.
+    ///
+    /// With `k == bit_size() - 1` this reverses the bits (see
+    /// [`Rbit`](trait.Rbit.html)), with `k == bit_size() - 8` it reverses the
+    /// bytes (see [`Rev`](trait.Rev.html)), and with `k == 4` it swaps the
+    /// nibbles of each byte.
+    ///
+    /// **Keywords**: Generalized reverse, bit permutation, butterfly.
+    ///
+    /// # Instructions
+    ///
+    /// - [`GREV`](https://github.com/riscv/riscv-bitmanip):
+    ///   - Description: Generalized reverse.
+    ///   - Architecture: RISC-V.
+    ///   - Instruction set: Zbp (draft).
+    ///   - Registers: 32/64 bit.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bitintr::*;
+    /// let n = 0b1101_0011_1110_1010u16;
+    ///
+    /// assert_eq!(n.grev(1), 0b1110_0011_1101_0101u16);
+    /// assert_eq!(n.grev(4), 0b0011_1101_1010_1110u16);
+    /// assert_eq!(n.grev(8), n.rev());
+    /// assert_eq!(n.grev(15), n.rbit());
+    /// assert_eq!(n.grev(15).grev(15), n);
+    /// ```
+    fn grev(self, k: u32) -> Self;
+}
+
+macro_rules! impl_grev {
+    ($id:ident, $uid:ident) => {
+        #[allow(clippy::use_self)]
+        impl Grev for $id {
+            #[inline]
+            fn grev(self, k: u32) -> Self {
+                const BIT_WIDTH: u32 =
+                    (crate::mem::size_of::<$id>() * 8) as u32;
+                let k = k & (BIT_WIDTH - 1);
+                if k == BIT_WIDTH - 1 {
+                    return crate::Rbit::rbit(self);
+                }
+                if k == BIT_WIDTH - 8 {
+                    return crate::Rev::rev(self);
+                }
+                grev_stages(u64::from(self as $uid), k) as Self
+            }
+        }
+    };
+}
+
+impl_grev!(u8, u8);
+impl_grev!(i8, u8);
+impl_grev!(u16, u16);
+impl_grev!(i16, u16);
+impl_grev!(u32, u32);
+impl_grev!(i32, u32);
+impl_grev!(u64, u64);
+impl_grev!(i64, u64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{xorshift64, SEED};
+
+    #[test]
+    fn grev_u16() {
+        for x in 0..=u16::MAX {
+            for k in 0..16 {
+                // Bit `i` moves to bit `i ^ k`:
+                let mut expected = 0_u16;
+                for i in 0..16 {
+                    expected |= ((x >> i) & 1) << (i ^ k);
+                }
+                let y = x.grev(k);
+                assert_eq!(y, expected, "{:#x} {}", x, k);
+                assert_eq!((x as i16).grev(k), expected as i16);
+                assert_eq!(y.grev(k), x);
+                // The `Rbit` and `Rev` fast paths:
+                assert_eq!(grev_stages(u64::from(x), k), u64::from(y));
+            }
+            assert_eq!(x.grev(16 + 3), x.grev(3));
+        }
+    }
+
+    #[test]
+    fn grev_fast_paths() {
+        let mut next = xorshift64(SEED);
+        for _ in 0..1000 {
+            let x = next();
+            for &k in &[7, 15, 31, 63, 8, 16, 24, 32, 48, 56] {
+                let y = x.grev(k);
+                assert_eq!(y, grev_stages(x, k));
+                assert_eq!(y.grev(k), x);
+                if k < 32 {
+                    let x = x as u32;
+                    assert_eq!(u64::from(x.grev(k)), grev_stages(x.into(), k));
+                    assert_eq!(x.grev(k).grev(k), x);
+                }
+            }
+            for k in 0..8 {
+                let x = x as u8;
+                assert_eq!(u64::from(x.grev(k)), grev_stages(x.into(), k));
+            }
+        }
+    }
+}
@@ -0,0 +1,630 @@
+//! Iterators over the bits of words.
+//!
+//! These iterators are implemented for all integer types _except_
+//! `u128/i128`, and for slices of them.
+
+use crate::{Blsi, Blsr, Lzcnt, Pdep, Pext, Popcnt, Tzcnt};
+use core::convert::TryFrom;
+use core::iter::FusedIterator;
+use core::ops::{BitAnd, Not};
+
+/// Iterator over the indices of the set bits of a word.
+///
+/// Yields the indices in increasing order, or in decreasing order when
+/// iterated from the back.
+///
+/// # Example
+///
+/// ```
+/// # use bitintr::iter::*;
+/// let mut it = SetBits::new(0b1010_0110_u8);
+/// assert_eq!(it.len(), 4);
+/// assert_eq!(it.next(), Some(1));
+/// assert_eq!(it.next_back(), Some(7));
+/// assert_eq!(it.len(), 2);
+/// assert_eq!(it.collect::<Vec<_>>(), [2, 5]);
+///
+/// assert_eq!(SetBits::new(i16::MIN).collect::<Vec<_>>(), [15]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct SetBits<T> {
+    bits: T,
+}
+
+impl<T> SetBits<T> {
+    /// Iterates over the set bits of `x`.
+    #[inline]
+    pub fn new(x: T) -> Self {
+        Self { bits: x }
+    }
+
+    /// Iterates over the remaining set bits as isolated bits.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bitintr::iter::*;
+    /// let mut it = SetBits::new(0b1010_0110_u8);
+    /// assert_eq!(it.next(), Some(1));
+    /// let bits = it.isolated().collect::<Vec<_>>();
+    /// assert_eq!(bits, [0b0000_0100, 0b0010_0000, 0b1000_0000]);
+    /// ```
+    #[inline]
+    pub fn isolated(self) -> IsolatedBits<T> {
+        IsolatedBits::new(self.bits)
+    }
+}
+
+/// Iterator over the set bits of a word as isolated bits.
+///
+/// Yields words with only one bit set, one for each bit set in the original
+/// word, in increasing order, or in decreasing order when iterated from the
+/// back.
+///
+/// # Example
+///
+/// ```
+/// # use bitintr::iter::*;
+/// let mut it = IsolatedBits::new(0b1010_0110_u8);
+/// assert_eq!(it.len(), 4);
+/// assert_eq!(it.next(), Some(0b0000_0010));
+/// assert_eq!(it.next_back(), Some(0b1000_0000));
+/// assert_eq!(it.collect::<Vec<_>>(), [0b0000_0100, 0b0010_0000]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct IsolatedBits<T> {
+    bits: T,
+}
+
+impl<T> IsolatedBits<T> {
+    /// Iterates over the set bits of `x`.
+    #[inline]
+    pub fn new(x: T) -> Self {
+        Self { bits: x }
+    }
+}
+
+/// Iterator over the indices of the set bits of a slice of words.
+///
+/// The index of the bit `j` of the word `i` is `i * BITS + j`, where `BITS`
+/// is the bit width of the words. Yields the indices in increasing order,
+/// or in decreasing order when iterated from the back.
+///
+/// The length of the iterator is computed by counting the bits set in the
+/// words that have not been visited yet, which takes time linear in their
+/// number.
+///
+/// # Example
+///
+/// ```
+/// # use bitintr::iter::*;
+/// let words = [0b0001_u8, 0, 0b1000_0010];
+/// let mut it = SliceSetBits::new(&words);
+/// assert_eq!(it.len(), 3);
+/// assert_eq!(it.next_back(), Some(23));
+/// assert_eq!(it.collect::<Vec<_>>(), [0, 17]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct SliceSetBits<'a, T> {
+    front: T,
+    front_base: usize,
+    back: T,
+    back_base: usize,
+    /// Words that have not been visited yet.
+    words: &'a [T],
+    /// Index of `words[0]` in the original slice.
+    offset: usize,
+}
+
+impl<'a, T: Default> SliceSetBits<'a, T> {
+    /// Iterates over the set bits of `words`.
+    #[inline]
+    pub fn new(words: &'a [T]) -> Self {
+        Self {
+            front: T::default(),
+            front_base: 0,
+            back: T::default(),
+            back_base: 0,
+            words,
+            offset: 0,
+        }
+    }
+}
+
+macro_rules! impl_iter {
+    ($id:ident) => {
+        impl Iterator for SetBits<$id> {
+            type Item = u32;
+            #[inline]
+            fn next(&mut self) -> Option<u32> {
+                if self.bits == 0 {
+                    return None;
+                }
+                let i = self.bits.tzcnt() as u32;
+                self.bits = self.bits.blsr();
+                Some(i)
+            }
+            #[inline]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                let n = self.bits.popcnt() as usize;
+                (n, Some(n))
+            }
+        }
+
+        impl DoubleEndedIterator for SetBits<$id> {
+            #[inline]
+            fn next_back(&mut self) -> Option<u32> {
+                if self.bits == 0 {
+                    return None;
+                }
+                const BIT_WIDTH: u32 =
+                    (crate::mem::size_of::<$id>() * 8) as u32;
+                let i = BIT_WIDTH - 1 - self.bits.lzcnt() as u32;
+                self.bits ^= (1 as $id) << i;
+                Some(i)
+            }
+        }
+
+        impl ExactSizeIterator for SetBits<$id> {}
+        impl FusedIterator for SetBits<$id> {}
+
+        impl Iterator for IsolatedBits<$id> {
+            type Item = $id;
+            #[inline]
+            fn next(&mut self) -> Option<$id> {
+                if self.bits == 0 {
+                    return None;
+                }
+                let b = self.bits.blsi();
+                self.bits ^= b;
+                Some(b)
+            }
+            #[inline]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                let n = self.bits.popcnt() as usize;
+                (n, Some(n))
+            }
+        }
+
+        impl DoubleEndedIterator for IsolatedBits<$id> {
+            #[inline]
+            fn next_back(&mut self) -> Option<$id> {
+                SetBits::new(self.bits).next_back().map(|i| {
+                    let b = (1 as $id) << i;
+                    self.bits ^= b;
+                    b
+                })
+            }
+        }
+
+        impl ExactSizeIterator for IsolatedBits<$id> {}
+        impl FusedIterator for IsolatedBits<$id> {}
+
+        impl<'a> Iterator for SliceSetBits<'a, $id> {
+            type Item = usize;
+            #[inline]
+            fn next(&mut self) -> Option<usize> {
+                const BIT_WIDTH: usize = crate::mem::size_of::<$id>() * 8;
+                loop {
+                    if self.front != 0 {
+                        let i = self.front.tzcnt() as usize;
+                        self.front = self.front.blsr();
+                        return Some(self.front_base + i);
+                    }
+                    if let Some((&w, rest)) = self.words.split_first() {
+                        self.front = w;
+                        self.front_base = self.offset * BIT_WIDTH;
+                        self.words = rest;
+                        self.offset += 1;
+                        continue;
+                    }
+                    // The only bits left are in the back word:
+                    let i = SetBits::new(self.back).next()? as usize;
+                    self.back = self.back.blsr();
+                    return Some(self.back_base + i);
+                }
+            }
+            #[inline]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                let n =
+                    self.words.iter().fold(0, |n, w| n + w.popcnt() as usize)
+                        + self.front.popcnt() as usize
+                        + self.back.popcnt() as usize;
+                (n, Some(n))
+            }
+        }
+
+        impl<'a> DoubleEndedIterator for SliceSetBits<'a, $id> {
+            #[inline]
+            fn next_back(&mut self) -> Option<usize> {
+                const BIT_WIDTH: usize = crate::mem::size_of::<$id>() * 8;
+                loop {
+                    let mut back = SetBits::new(self.back);
+                    if let Some(i) = back.next_back() {
+                        self.back = back.bits;
+                        return Some(self.back_base + i as usize);
+                    }
+                    if let Some((&w, rest)) = self.words.split_last() {
+                        self.back = w;
+                        self.back_base =
+                            (self.offset + rest.len()) * BIT_WIDTH;
+                        self.words = rest;
+                        continue;
+                    }
+                    // The only bits left are in the front word:
+                    let mut front = SetBits::new(self.front);
+                    let i = front.next_back()?;
+                    self.front = front.bits;
+                    return Some(self.front_base + i as usize);
+                }
+            }
+        }
+
+        impl<'a> ExactSizeIterator for SliceSetBits<'a, $id> {}
+        impl<'a> FusedIterator for SliceSetBits<'a, $id> {}
+    };
+}
+
+impl_all!(impl_iter: u8, u16, u32, u64, i8, i16, i32, i64);
+
+/// Iterator over the submasks of a mask.
+///
+/// Yields every `x` such that `x & mask == x`, that is, every subset of the
+/// bits of `mask`, including `0` and `mask`, in increasing order. Iterate
+/// with [`rev`](https://doc.rust-lang.org/core/iter/trait.Iterator.html#method.rev)
+/// for the decreasing order, which is the classic `s = (s - 1) & mask` loop.
+///
+/// The `k`-th submask is `k.pdep(mask)`, so [`nth`](#method.nth) is O(1)
+/// when [`Pdep`](../trait.Pdep.html) is lowered to an instruction.
+///
+/// The number of submasks is `2^popcnt(mask)`, which might not fit in a
+/// `usize`, so this iterator is not an `ExactSizeIterator`, but its
+/// `size_hint` is exact whenever it does.
+///
+/// # Example
+///
+/// ```
+/// # use bitintr::iter::*;
+/// let subs = Submasks::new(0b1010_u8).collect::<Vec<_>>();
+/// assert_eq!(subs, [0b0000, 0b0010, 0b1000, 0b1010]);
+///
+/// let subs = Submasks::new(0b1010_u8).rev().collect::<Vec<_>>();
+/// assert_eq!(subs, [0b1010, 0b1000, 0b0010, 0b0000]);
+///
+/// // Skipping is O(1) with BMI2:
+/// let mut it = Submasks::new(0xFFFF_0000_0000_FFFF_u64);
+/// assert_eq!(it.nth(0x1_0000), Some(0x0001_0000_0000_0000));
+/// assert_eq!(it.next(), Some(0x0001_0000_0000_0001));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Submasks<T> {
+    mask: T,
+    /// Next submask from the front.
+    front: T,
+    /// Next submask from the back.
+    back: T,
+    done: bool,
+}
+
+impl<T: Copy + Default> Submasks<T> {
+    /// Iterates over the submasks of `mask`.
+    #[inline]
+    pub fn new(mask: T) -> Self {
+        Self {
+            mask,
+            front: T::default(),
+            back: mask,
+            done: false,
+        }
+    }
+}
+
+/// Iterator over the supersets of a mask within a universe.
+///
+/// Yields every `x` such that `x & mask == mask` and
+/// `x & (mask | universe) == x`, in increasing order, or in decreasing
+/// order when iterated from the back. These are `mask | s` for every
+/// submask `s` of `universe & !mask` (see [`Submasks`](struct.Submasks.html)).
+///
+/// # Example
+///
+/// ```
+/// # use bitintr::iter::*;
+/// let sups = Supersets::new(0b0010_u8, 0b1011).collect::<Vec<_>>();
+/// assert_eq!(sups, [0b0010, 0b0011, 0b1010, 0b1011]);
+///
+/// // All the 2^6 supersets of `0b11` within a byte:
+/// assert_eq!(Supersets::new(0b11_u8, !0).count(), 64);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Supersets<T> {
+    mask: T,
+    free: Submasks<T>,
+}
+
+impl<T> Supersets<T>
+where
+    T: Copy + Default + Not<Output = T> + BitAnd<Output = T>,
+{
+    /// Iterates over the supersets of `mask` within `universe`.
+    #[inline]
+    pub fn new(mask: T, universe: T) -> Self {
+        Self {
+            mask,
+            free: Submasks::new(universe & !mask),
+        }
+    }
+}
+
+macro_rules! impl_subsets {
+    ($id:ident, $uid:ident) => {
+        impl Submasks<$id> {
+            /// Index of the submask `x` in increasing order.
+            #[inline]
+            fn index(&self, x: $id) -> $uid {
+                (x as $uid).pext(self.mask as $uid)
+            }
+        }
+
+        impl Iterator for Submasks<$id> {
+            type Item = $id;
+            #[inline]
+            fn next(&mut self) -> Option<$id> {
+                if self.done {
+                    return None;
+                }
+                let s = self.front;
+                if s == self.back {
+                    self.done = true;
+                } else {
+                    // Adds one to the bits of `s` selected by `mask`:
+                    self.front = s.wrapping_sub(self.mask) & self.mask;
+                }
+                Some(s)
+            }
+            #[inline]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                if self.done {
+                    return (0, Some(0));
+                }
+                let n = self.index(self.back) - self.index(self.front);
+                match usize::try_from(n).ok().and_then(|n| n.checked_add(1)) {
+                    Some(n) => (n, Some(n)),
+                    None => (usize::MAX, None),
+                }
+            }
+            #[inline]
+            fn nth(&mut self, n: usize) -> Option<$id> {
+                if self.done {
+                    return None;
+                }
+                let k = <$uid>::try_from(n)
+                    .ok()
+                    .and_then(|n| self.index(self.front).checked_add(n));
+                match k {
+                    Some(k) if k <= self.index(self.back) => {
+                        self.front = (k as $id).pdep(self.mask);
+                        self.next()
+                    }
+                    _ => {
+                        self.done = true;
+                        None
+                    }
+                }
+            }
+            #[inline]
+            fn last(mut self) -> Option<$id> {
+                self.next_back()
+            }
+        }
+
+        impl DoubleEndedIterator for Submasks<$id> {
+            #[inline]
+            fn next_back(&mut self) -> Option<$id> {
+                if self.done {
+                    return None;
+                }
+                let s = self.back;
+                if s == self.front {
+                    self.done = true;
+                } else {
+                    self.back = s.wrapping_sub(1) & self.mask;
+                }
+                Some(s)
+            }
+            #[inline]
+            fn nth_back(&mut self, n: usize) -> Option<$id> {
+                if self.done {
+                    return None;
+                }
+                let k = <$uid>::try_from(n)
+                    .ok()
+                    .and_then(|n| self.index(self.back).checked_sub(n));
+                match k {
+                    Some(k) if k >= self.index(self.front) => {
+                        self.back = (k as $id).pdep(self.mask);
+                        self.next_back()
+                    }
+                    _ => {
+                        self.done = true;
+                        None
+                    }
+                }
+            }
+        }
+
+        impl FusedIterator for Submasks<$id> {}
+
+        impl Iterator for Supersets<$id> {
+            type Item = $id;
+            #[inline]
+            fn next(&mut self) -> Option<$id> {
+                self.free.next().map(|s| s | self.mask)
+            }
+            #[inline]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.free.size_hint()
+            }
+            #[inline]
+            fn nth(&mut self, n: usize) -> Option<$id> {
+                self.free.nth(n).map(|s| s | self.mask)
+            }
+            #[inline]
+            fn last(self) -> Option<$id> {
+                let mask = self.mask;
+                self.free.last().map(|s| s | mask)
+            }
+        }
+
+        impl DoubleEndedIterator for Supersets<$id> {
+            #[inline]
+            fn next_back(&mut self) -> Option<$id> {
+                self.free.next_back().map(|s| s | self.mask)
+            }
+            #[inline]
+            fn nth_back(&mut self, n: usize) -> Option<$id> {
+                self.free.nth_back(n).map(|s| s | self.mask)
+            }
+        }
+
+        impl FusedIterator for Supersets<$id> {}
+    };
+}
+
+impl_subsets!(u8, u8);
+impl_subsets!(u16, u16);
+impl_subsets!(u32, u32);
+impl_subsets!(u64, u64);
+impl_subsets!(i8, u8);
+impl_subsets!(i16, u16);
+impl_subsets!(i32, u32);
+impl_subsets!(i64, u64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_bits_u8() {
+        for x in 0..=u8::MAX {
+            let naive = (0..8).filter(|&i| x & (1 << i) != 0);
+            assert!(SetBits::new(x).eq(naive.clone()));
+            assert!(SetBits::new(x as i8).eq(naive.clone()));
+            assert!(SetBits::new(x).rev().eq(naive.clone().rev()));
+            assert!(IsolatedBits::new(x).eq(naive.clone().map(|i| 1 << i)));
+            assert!(IsolatedBits::new(x as i8)
+                .rev()
+                .eq(naive.clone().rev().map(|i| (1 << i) as i8)));
+            assert_eq!(SetBits::new(x).len(), naive.count());
+        }
+    }
+
+    #[test]
+    fn set_bits_slice() {
+        const WORDS: &[u64] = &[
+            0x8000_0000_0000_0001,
+            0,
+            0,
+            u64::MAX,
+            0x0123_4567_89AB_CDEF,
+            0,
+        ];
+        for n in 0..=WORDS.len() {
+            let words = &WORDS[..n];
+            let mut buf = [0; 6 * 64];
+            let mut len = 0;
+            for i in 0..n * 64 {
+                if words[i / 64] & (1 << (i % 64)) != 0 {
+                    buf[len] = i;
+                    len += 1;
+                }
+            }
+            let naive = &buf[..len];
+            let it = SliceSetBits::new(words);
+            assert_eq!(it.len(), naive.len());
+            assert!(it.clone().eq(naive.iter().cloned()));
+            assert!(it.rev().eq(naive.iter().rev().cloned()));
+            // Alternate between both ends:
+            for k in 0..naive.len() {
+                let mut it = SliceSetBits::new(words);
+                let (mut lo, mut hi) = (0, naive.len());
+                for step in 0..naive.len() {
+                    if (step + k) % 3 == 0 {
+                        hi -= 1;
+                        assert_eq!(it.next_back(), Some(naive[hi]));
+                    } else {
+                        assert_eq!(it.next(), Some(naive[lo]));
+                        lo += 1;
+                    }
+                    assert_eq!(it.len(), hi - lo);
+                }
+                assert_eq!(it.next(), None);
+                assert_eq!(it.next_back(), None);
+            }
+        }
+    }
+
+    #[test]
+    fn submasks_u8() {
+        for m in 0..=u8::MAX {
+            let naive = (0..=u8::MAX).filter(|&x| x & m == x);
+            assert!(Submasks::new(m).eq(naive.clone()));
+            assert!(Submasks::new(m).rev().eq(naive.clone().rev()));
+            assert!(Submasks::new(m as i8).eq(naive.clone().map(|x| x as i8)));
+            let n = naive.clone().count();
+            assert_eq!(Submasks::new(m).size_hint(), (n, Some(n)));
+            for u in 0..=u8::MAX {
+                let naive =
+                    (0..=u8::MAX).filter(|&x| x & m == m && x & (m | u) == x);
+                assert!(Supersets::new(m, u).eq(naive.clone()));
+                assert!(Supersets::new(m, u).rev().eq(naive.rev()));
+            }
+        }
+    }
+
+    #[test]
+    fn submasks_nth() {
+        for m in 0..=u8::MAX {
+            let n = Submasks::new(m).count();
+            for i in 0..=n + 1 {
+                for j in 0..=n + 1 {
+                    let mut it = Submasks::new(m);
+                    let mut naive = Submasks::new(m);
+                    assert_eq!(it.nth(i), naive.by_ref().nth_naive(i));
+                    assert_eq!(
+                        it.nth_back(j),
+                        naive.by_ref().rev().nth_naive(j)
+                    );
+                    assert_eq!(it.size_hint(), naive.size_hint());
+                    assert!(it.eq(naive));
+                }
+            }
+        }
+        let mut it = Supersets::new(i64::MIN, -1);
+        assert_eq!(it.nth(2), Some(i64::MIN | 2));
+        assert_eq!(it.last(), Some(-1));
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn submasks_nth_u64() {
+        let mut it = Submasks::new(u64::MAX);
+        assert_eq!(it.size_hint().1, None);
+        assert_eq!(it.nth(usize::MAX - 2), Some(usize::MAX as u64 - 2));
+        assert_eq!(it.size_hint(), (2, Some(2)));
+        assert_eq!(it.nth_back(1), Some(u64::MAX - 1));
+        assert_eq!(it.next(), None);
+    }
+
+    trait NthNaive: Iterator + Sized {
+        /// `nth` implemented with `next`.
+        fn nth_naive(mut self, n: usize) -> Option<Self::Item> {
+            for _ in 0..n {
+                self.next()?;
+            }
+            self.next()
+        }
+    }
+
+    impl<I: Iterator> NthNaive for I {}
+}
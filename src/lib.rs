@@ -3,17 +3,25 @@
 //! [@crates.io](https://crates.io/crates/bitintr)).
 //!
 //! The intrinsics are named after their CPU instruction and organized in
-//! traits of the same name. These traits are implemented for all integer types
-//! _except_ `u128/i128`.
+//! traits of the same name. Unless stated otherwise, these traits are
+//! implemented for all integer types _except_ `u128/i128`.
 //!
 //! The `std::arch` intrinsics are used when the required features are enabled
 //! in the target. You might manually enable features via `-C
 //! target-feature=+...` and/or `-C target-cpu=...`.
+//!
+//! On nightly Rust, some of the traits are also implemented lane-wise for
+//! the `core::simd` vector types.
 #![no_std]
-#![cfg_attr(bitintr_nightly, feature(stdsimd))]
+#![cfg_attr(bitintr_nightly, feature(portable_simd))]
+#![cfg_attr(
+    all(bitintr_nightly, target_arch = "powerpc64"),
+    feature(asm_experimental_arch)
+)]
 
 use core::{marker, mem};
 
+#[allow(unused_imports)]
 mod arch {
     #[cfg(target_arch = "x86")]
     pub use core::arch::x86::*;
@@ -102,3 +110,106 @@ pub use self::t1mskc::T1mskc;
 
 mod tzmsk;
 pub use self::tzmsk::Tzmsk;
+
+mod grev;
+pub use self::grev::Grev;
+
+mod gorc;
+pub use self::gorc::Gorc;
+
+mod shfl;
+pub use self::shfl::Shfl;
+
+mod unshfl;
+pub use self::unshfl::Unshfl;
+
+mod bgrp;
+pub use self::bgrp::Bgrp;
+
+mod cntlzdm;
+pub use self::cntlzdm::Cntlzdm;
+
+mod cnttzdm;
+pub use self::cnttzdm::Cnttzdm;
+
+mod cfuged;
+pub use self::cfuged::Cfuged;
+
+mod bperm;
+pub use self::bperm::Bperm;
+
+mod multishift;
+pub use self::multishift::Multishift;
+
+mod gf2p8mul;
+pub use self::gf2p8mul::Gf2p8Mul;
+
+mod gf2p8affine;
+pub use self::gf2p8affine::Gf2p8Affine;
+
+mod rorx;
+pub use self::rorx::Rorx;
+
+mod fshl;
+pub use self::fshl::Fshl;
+
+mod fshr;
+pub use self::fshr::Fshr;
+
+mod shlx;
+pub use self::shlx::Shlx;
+
+mod shrx;
+pub use self::shrx::Shrx;
+
+mod sarx;
+pub use self::sarx::Sarx;
+
+mod adc;
+pub use self::adc::Adc;
+
+mod sbb;
+pub use self::sbb::Sbb;
+
+mod divx;
+pub use self::divx::Divx;
+
+mod divmagic;
+pub use self::divmagic::DivMagic;
+
+mod crc32c;
+pub use self::crc32c::Crc32c;
+
+mod crc32;
+pub use self::crc32::Crc32;
+
+mod clmul;
+pub use self::clmul::Clmul;
+
+mod crc;
+pub use self::crc::Crc;
+
+mod parity;
+pub use self::parity::Parity;
+
+mod select;
+pub use self::select::Select;
+
+mod rank;
+pub use self::rank::Rank;
+
+pub mod bmat;
+pub mod comb;
+pub mod ecc;
+pub mod gf2;
+pub mod iter;
+pub mod limbs;
+pub mod range;
+
+mod word;
+
+#[cfg(test)]
+mod test_util;
+
+#[cfg(bitintr_nightly)]
+mod simd;
@@ -0,0 +1,429 @@
+//! Multi-precision arithmetic on slices of limbs.
+//!
+//! A multi-precision natural number is stored as a slice of `u64` limbs in
+//! little-endian order, that is, the limb at index `i` has weight
+//! `2^(64 * i)`. These are the low-level kernels of GMP's `mpn` layer: they
+//! never allocate, and the caller is responsible for sizing the output
+//! slices.
+//!
+//! # Panics
+//!
+//! All functions panic if the lengths of the slices do not match the ones
+//! documented for each function.
+
+use crate::{Adc, Fshl, Fshr, Mulx, Sbb};
+
+/// Adds `a` and `b` into `r`, returning the carry out.
+///
+/// All slices must have the same length.
+///
+/// # Example
+///
+/// ```
+/// # use bitintr::limbs::*;
+/// let a = [u64::MAX, 1];
+/// let b = [1, 2];
+/// let mut r = [0; 2];
+/// assert!(!add_n(&mut r, &a, &b));
+/// assert_eq!(r, [0, 4]);
+/// ```
+#[inline]
+pub fn add_n(r: &mut [u64], a: &[u64], b: &[u64]) -> bool {
+    assert!(r.len() == a.len() && a.len() == b.len());
+    let mut carry = false;
+    for ((r, &a), &b) in r.iter_mut().zip(a).zip(b) {
+        let (s, c) = a.adc(b, carry);
+        *r = s;
+        carry = c;
+    }
+    carry
+}
+
+/// Subtracts `b` from `a` into `r`, returning the borrow out.
+///
+/// All slices must have the same length.
+///
+/// # Example
+///
+/// ```
+/// # use bitintr::limbs::*;
+/// let a = [0, 4];
+/// let b = [1, 2];
+/// let mut r = [0; 2];
+/// assert!(!sub_n(&mut r, &a, &b));
+/// assert_eq!(r, [u64::MAX, 1]);
+/// assert!(sub_n(&mut r, &b, &a));
+/// ```
+#[inline]
+pub fn sub_n(r: &mut [u64], a: &[u64], b: &[u64]) -> bool {
+    assert!(r.len() == a.len() && a.len() == b.len());
+    let mut borrow = false;
+    for ((r, &a), &b) in r.iter_mut().zip(a).zip(b) {
+        let (d, c) = a.sbb(b, borrow);
+        *r = d;
+        borrow = c;
+    }
+    borrow
+}
+
+/// Multiplies `a` by the limb `b` into `r`, returning the high limb of the
+/// product.
+///
+/// `r` and `a` must have the same length.
+///
+/// # Example
+///
+/// ```
+/// # use bitintr::limbs::*;
+/// let a = [u64::MAX, 1];
+/// let mut r = [0; 2];
+/// assert_eq!(mul_1(&mut r, &a, 4), 0);
+/// assert_eq!(r, [u64::MAX - 3, 7]);
+/// ```
+#[inline]
+pub fn mul_1(r: &mut [u64], a: &[u64], b: u64) -> u64 {
+    assert_eq!(r.len(), a.len());
+    let mut carry = 0;
+    for (r, &a) in r.iter_mut().zip(a) {
+        let (lo, hi) = a.mulx(b);
+        let (lo, c) = lo.adc(carry, false);
+        *r = lo;
+        carry = hi + c as u64;
+    }
+    carry
+}
+
+cfg_if! {
+    if #[cfg(all(
+        target_arch = "x86_64",
+        target_feature = "bmi2",
+        target_feature = "adx"
+    ))] {
+        #[inline]
+        fn addmul_1_(r: &mut [u64], a: &[u64], b: u64) -> u64 {
+            if a.is_empty() {
+                return 0;
+            }
+            let carry: u64;
+            // UNSAFETY: `r` and `a` have the same non-zero length, and the
+            // loop reads and writes exactly that many limbs of each.
+            //
+            // The high half of each product is added with `ADCX`, which only
+            // uses CF, and the limb of `r` with `ADOX`, which only uses OF,
+            // so that the two carry chains do not serialize. The loop
+            // control uses `LEA` and `JRCXZ`, which do not modify the flags.
+            unsafe {
+                core::arch::asm!(
+                    "xor {c:e}, {c:e}",
+                    "2:",
+                    "mulx {hi}, {lo}, qword ptr [{a}]",
+                    "adcx {lo}, {c}",
+                    "adox {lo}, qword ptr [{r}]",
+                    "mov qword ptr [{r}], {lo}",
+                    "mov {c}, {hi}",
+                    "lea {a}, [{a} + 8]",
+                    "lea {r}, [{r} + 8]",
+                    "lea rcx, [rcx - 1]",
+                    "jrcxz 3f",
+                    "jmp 2b",
+                    "3:",
+                    "mov {lo:e}, 0",
+                    "adcx {c}, {lo}",
+                    "adox {c}, {lo}",
+                    in("rdx") b,
+                    inout("rcx") a.len() => _,
+                    a = inout(reg) a.as_ptr() => _,
+                    r = inout(reg) r.as_mut_ptr() => _,
+                    c = out(reg) carry,
+                    lo = out(reg) _,
+                    hi = out(reg) _,
+                    options(nostack),
+                );
+            }
+            carry
+        }
+    } else {
+        #[inline]
+        fn addmul_1_(r: &mut [u64], a: &[u64], b: u64) -> u64 {
+            let mut carry = 0;
+            for (r, &a) in r.iter_mut().zip(a) {
+                let (lo, hi) = a.mulx(b);
+                let (lo, c0) = lo.adc(carry, false);
+                let (lo, c1) = lo.adc(*r, false);
+                *r = lo;
+                carry = hi + c0 as u64 + c1 as u64;
+            }
+            carry
+        }
+    }
+}
+
+/// Adds the product of `a` and the limb `b` to `r`, returning the carry
+/// limb.
+///
+/// `r` and `a` must have the same length.
+///
+/// # Instructions
+///
+/// - [`MULX`](http://www.felixcloutier.com/x86/MULX.html),
+///   [`ADCX`](http://www.felixcloutier.com/x86/ADCX.html),
+///   [`ADOX`](http://www.felixcloutier.com/x86/ADOX.html):
+///   - Description: Multiply and add with two independent carry chains.
+///   - Architecture: x86.
+///   - Instruction set: BMI2, ADX.
+///   - Registers: 64 bit.
+///
+/// # Example
+///
+/// ```
+/// # use bitintr::limbs::*;
+/// let a = [u64::MAX, u64::MAX];
+/// let mut r = [u64::MAX, u64::MAX];
+/// assert_eq!(addmul_1(&mut r, &a, u64::MAX), u64::MAX);
+/// assert_eq!(r, [0, u64::MAX]);
+/// ```
+#[inline]
+pub fn addmul_1(r: &mut [u64], a: &[u64], b: u64) -> u64 {
+    assert_eq!(r.len(), a.len());
+    addmul_1_(r, a, b)
+}
+
+/// Subtracts the product of `a` and the limb `b` from `r`, returning the
+/// borrow limb.
+///
+/// `r` and `a` must have the same length.
+///
+/// # Example
+///
+/// ```
+/// # use bitintr::limbs::*;
+/// let a = [3, 0];
+/// let mut r = [5, 1];
+/// assert_eq!(submul_1(&mut r, &a, 2), 0);
+/// assert_eq!(r, [u64::MAX, 0]);
+/// assert_eq!(submul_1(&mut r, &[0, 1], 1), 1);
+/// ```
+#[inline]
+pub fn submul_1(r: &mut [u64], a: &[u64], b: u64) -> u64 {
+    assert_eq!(r.len(), a.len());
+    let mut borrow = 0;
+    for (r, &a) in r.iter_mut().zip(a) {
+        let (lo, hi) = a.mulx(b);
+        let (lo, c0) = lo.adc(borrow, false);
+        let (d, c1) = r.sbb(lo, false);
+        *r = d;
+        borrow = hi + c0 as u64 + c1 as u64;
+    }
+    borrow
+}
+
+/// Shifts `a` left by `bits` into `r`, returning the bits shifted out in the
+/// low bits of the result.
+///
+/// `r` and `a` must have the same length, and `bits` must be smaller than
+/// 64.
+///
+/// # Example
+///
+/// ```
+/// # use bitintr::limbs::*;
+/// let a = [0x8000_0000_0000_0001, 0xF000_0000_0000_0000];
+/// let mut r = [0; 2];
+/// assert_eq!(lshift(&mut r, &a, 4), 0xF);
+/// assert_eq!(r, [0x10, 0x8]);
+/// ```
+#[inline]
+pub fn lshift(r: &mut [u64], a: &[u64], bits: u32) -> u64 {
+    assert_eq!(r.len(), a.len());
+    assert!(bits < 64);
+    let mut lo = 0;
+    for (r, &a) in r.iter_mut().zip(a) {
+        *r = a.fshl(lo, bits);
+        lo = a;
+    }
+    0.fshl(lo, bits)
+}
+
+/// Shifts `a` right by `bits` into `r`, returning the bits shifted out in
+/// the high bits of the result.
+///
+/// `r` and `a` must have the same length, and `bits` must be smaller than
+/// 64.
+///
+/// # Example
+///
+/// ```
+/// # use bitintr::limbs::*;
+/// let a = [0x8000_0000_0000_0001, 0xF000_0000_0000_0000];
+/// let mut r = [0; 2];
+/// assert_eq!(rshift(&mut r, &a, 4), 0x1000_0000_0000_0000);
+/// assert_eq!(r, [0x0800_0000_0000_0000, 0x0F00_0000_0000_0000]);
+/// ```
+#[inline]
+pub fn rshift(r: &mut [u64], a: &[u64], bits: u32) -> u64 {
+    assert_eq!(r.len(), a.len());
+    assert!(bits < 64);
+    let mut hi = 0;
+    for (r, &a) in r.iter_mut().zip(a).rev() {
+        *r = hi.fshr(a, bits);
+        hi = a;
+    }
+    hi.fshr(0, bits)
+}
+
+/// Multiplies `a` and `b` into `r` using the schoolbook method.
+///
+/// `r` must be as long as `a` and `b` together.
+///
+/// # Example
+///
+/// ```
+/// # use bitintr::limbs::*;
+/// let a = [u64::MAX, u64::MAX];
+/// let b = [u64::MAX];
+/// let mut r = [0; 3];
+/// mul(&mut r, &a, &b);
+/// assert_eq!(r, [1, u64::MAX, u64::MAX - 1]);
+/// ```
+#[inline]
+pub fn mul(r: &mut [u64], a: &[u64], b: &[u64]) {
+    assert_eq!(r.len(), a.len() + b.len());
+    let n = a.len();
+    for x in r[n..].iter_mut() {
+        *x = 0;
+    }
+    if b.is_empty() {
+        return;
+    }
+    r[n] = mul_1(&mut r[..n], a, b[0]);
+    for (j, &b) in b.iter().enumerate().skip(1) {
+        r[j + n] = addmul_1(&mut r[j..j + n], a, b);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::xorshift64;
+
+    // Naive model: natural numbers as little-endian 32-bit digits.
+    const N: usize = 4;
+    type Digits = [u64; 4 * N + 2];
+
+    fn to_digits(a: &[u64]) -> Digits {
+        let mut d = [0; 4 * N + 2];
+        for (i, &a) in a.iter().enumerate() {
+            d[2 * i] = a & 0xFFFF_FFFF;
+            d[2 * i + 1] = a >> 32;
+        }
+        d
+    }
+
+    fn from_digits(d: &Digits, limbs: usize) -> ([u64; 2 * N], u64) {
+        let mut a = [0; 2 * N];
+        for (i, a) in a.iter_mut().enumerate().take(limbs) {
+            *a = d[2 * i] | (d[2 * i + 1] << 32);
+        }
+        (a, d[2 * limbs] | (d[2 * limbs + 1] << 32))
+    }
+
+    fn naive_add(a: &Digits, b: &Digits) -> Digits {
+        let mut r = [0; 4 * N + 2];
+        let mut c = 0;
+        for i in 0..r.len() {
+            let s = a[i] + b[i] + c;
+            r[i] = s & 0xFFFF_FFFF;
+            c = s >> 32;
+        }
+        r
+    }
+
+    fn naive_mul(a: &Digits, b: &Digits) -> Digits {
+        let mut r = [0; 4 * N + 2];
+        for i in 0..r.len() {
+            let mut c = 0;
+            for j in 0..r.len() - i {
+                let s = r[i + j] + a[i] * b[j] + c;
+                r[i + j] = s & 0xFFFF_FFFF;
+                c = s >> 32;
+            }
+        }
+        r
+    }
+
+    fn inputs() -> impl Iterator<Item = [u64; N]> {
+        const SEEDS: &[u64] = &[1, 0x0123_4567_89AB_CDEF, 0xDEAD_BEEF_F00D];
+        const EDGE: &[[u64; N]] =
+            &[[0; N], [u64::MAX; N], [1, 0, 0, u64::MAX]];
+        let random = SEEDS.iter().map(|&s| {
+            let mut next = xorshift64(s);
+            let mut a = [0; N];
+            for a in a.iter_mut() {
+                *a = next();
+            }
+            a
+        });
+        EDGE.iter().copied().chain(random)
+    }
+
+    #[test]
+    fn add_sub() {
+        for a in inputs() {
+            for b in inputs() {
+                let mut r = [0; N];
+                let c = add_n(&mut r, &a, &b);
+                let expected = naive_add(&to_digits(&a), &to_digits(&b));
+                let (e, ec) = from_digits(&expected, N);
+                assert_eq!((&r[..], c as u64), (&e[..N], ec));
+
+                let mut d = [0; N];
+                assert!(!sub_n(&mut d, &r, &b) || c);
+                assert_eq!(d, a);
+            }
+        }
+    }
+
+    #[test]
+    fn mul_addmul_submul() {
+        for a in inputs() {
+            for b in inputs() {
+                let (da, db) = (to_digits(&a), to_digits(&b));
+                for &k in b.iter() {
+                    let dk = to_digits(&[k]);
+                    let mut r = [0; N];
+                    let hi = mul_1(&mut r, &a, k);
+                    let (e, eh) = from_digits(&naive_mul(&da, &dk), N);
+                    assert_eq!((&r[..], hi), (&e[..N], eh));
+
+                    let mut r = b;
+                    let hi = addmul_1(&mut r, &a, k);
+                    let expected = naive_add(&naive_mul(&da, &dk), &db);
+                    let (e, eh) = from_digits(&expected, N);
+                    assert_eq!((&r[..], hi), (&e[..N], eh));
+
+                    let borrow = submul_1(&mut r, &a, k);
+                    assert_eq!((r, borrow), (b, hi));
+                }
+
+                let mut r = [0; 2 * N];
+                mul(&mut r, &a, &b);
+                let (e, _) = from_digits(&naive_mul(&da, &db), 2 * N);
+                assert_eq!(r, e);
+            }
+        }
+    }
+
+    #[test]
+    fn shifts() {
+        for a in inputs() {
+            for bits in 0..64 {
+                let mut l = [0; N];
+                let out = lshift(&mut l, &a, bits);
+                let mut r = [0; N];
+                assert_eq!(rshift(&mut r, &l, bits), 0);
+                assert_eq!(r[N - 1] | (out << (63 - bits) << 1), a[N - 1]);
+                assert_eq!(&r[..N - 1], &a[..N - 1]);
+            }
+        }
+    }
+}
@@ -24,7 +24,7 @@ pub trait Lzcnt {
     ///   - Instruction set: ABM, BMI.
     ///   - Registers: 16/32/64 bit.
     /// - Note: This instruction is officially part of BMI1 but Intel (and
-    /// AMD)   CPUs advertise it as being part of ABM.
+    ///   AMD) CPUs advertise it as being part of ABM.
     ///
     /// - [`CLZ`](https://www.pjrc.com/teensy/beta/
     ///   DDI0403D_arm_architecture_v7m_reference_manual.pdf):
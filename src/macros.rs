@@ -61,3 +61,29 @@ macro_rules! cfg_if {
         $(#[$m] $it)*
     };
 }
+
+/// Applies the three-register Power ISA 3.1 instruction `$instr` to the
+/// operands `$a: $ty` and `$b: $ty` zero-extended to 64 bits, and truncates
+/// the result back to `$ty`.
+#[cfg(all(
+    target_arch = "powerpc64",
+    target_feature = "power10-vector",
+    bitintr_nightly
+))]
+macro_rules! power10_asm {
+    ($instr:literal, $ty:ty, $a:expr, $b:expr) => {{
+        const MASK: u64 = !0 >> (64 - crate::mem::size_of::<$ty>() * 8);
+        let r: u64;
+        // UNSAFETY: the instruction only reads and writes registers.
+        unsafe {
+            core::arch::asm!(
+                concat!($instr, " {0}, {1}, {2}"),
+                lateout(reg) r,
+                in(reg) ($a as u64) & MASK,
+                in(reg) ($b as u64) & MASK,
+                options(pure, nomem, nostack),
+            );
+        }
+        r as $ty
+    }};
+}
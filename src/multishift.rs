@@ -0,0 +1,134 @@
+//! multishift
+
+/// Multi-shift bytes
+pub trait Multishift {
+    /// Extracts eight unaligned 8-bit fields.
+    ///
+    /// Byte `j` of the result is the 8-bit field of `self` starting at the
+    /// bit offset given by byte `j` of `offsets`. Only the low 6 bits of each
+    /// offset are used, and fields starting at offsets greater than 56 wrap
+    /// around to the least significant bits of `self`.
+    ///
+    /// For offsets in `[0, 56]`, byte `j` of the result is
+    /// `self.bextr(offset_j, 8)`.
+    ///
+    /// **Keywords**: Multi-shift, unaligned byte extract, bit unpacking.
+    ///
+    /// # Instructions
+    ///
+    /// - [`VPMULTISHIFTQB`](http://www.felixcloutier.com/x86/VPMULTISHIFTQB.html):
+    ///   - Description: Select packed unaligned bytes from quadword sources.
+    ///   - Architecture: x86.
+    ///   - Instruction set: AVX-512 VBMI.
+    ///   - Registers: 128/256/512 bit.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bitintr::*;
+    /// let n = 0x0123_4567_89AB_CDEFu64;
+    ///
+    /// assert_eq!(n.multishift(0x38_30_28_20_18_10_08_00), n);
+    /// assert_eq!(n.multishift(0x04) & 0xFF, 0xDE);
+    /// // Fields wrap around:
+    /// assert_eq!(n.multishift(0x3C) & 0xFF, 0xF0);
+    /// ```
+    fn multishift(self, offsets: u64) -> u64;
+}
+
+cfg_if! {
+    if #[cfg(all(
+        target_arch = "x86_64",
+        target_feature = "avx512vbmi",
+        target_feature = "avx512vl"
+    ))] {
+        #[inline]
+        #[target_feature(enable = "avx512vbmi,avx512vl")]
+        unsafe fn multishift_(value: u64, offsets: u64) -> u64 {
+            use crate::arch::*;
+            let r = _mm_multishift_epi64_epi8(
+                _mm_cvtsi64_si128(offsets as i64),
+                _mm_cvtsi64_si128(value as i64),
+            );
+            _mm_cvtsi128_si64(r) as u64
+        }
+    } else if #[cfg(all(
+        target_arch = "x86_64",
+        target_feature = "avx512vbmi"
+    ))] {
+        #[inline]
+        #[target_feature(enable = "avx512vbmi")]
+        unsafe fn multishift_(value: u64, offsets: u64) -> u64 {
+            use crate::arch::*;
+            let r = _mm512_multishift_epi64_epi8(
+                _mm512_set1_epi64(offsets as i64),
+                _mm512_set1_epi64(value as i64),
+            );
+            _mm_cvtsi128_si64(_mm512_castsi512_si128(r)) as u64
+        }
+    } else {
+        #[inline]
+        fn multishift_(value: u64, offsets: u64) -> u64 {
+            use crate::Bextr;
+            let mut r = 0;
+            for j in 0..8 {
+                let o = (offsets >> (8 * j)) & 0x3F;
+                let mut b = value.bextr(o, 8);
+                if o > 56 {
+                    b |= value.bextr(0, o - 56) << (64 - o);
+                }
+                r |= b << (8 * j);
+            }
+            r
+        }
+    }
+}
+
+impl Multishift for u64 {
+    #[inline]
+    #[allow(unused_unsafe)]
+    fn multishift(self, offsets: u64) -> u64 {
+        // UNSAFETY: this is always safe, because
+        // the unsafe `#[target_feature]` function
+        // is only generated when the feature is
+        // statically-enabled at compile-time.
+        unsafe { multishift_(self, offsets) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Bextr, Multishift};
+
+    #[test]
+    fn multishift_bextr() {
+        let words = [
+            0,
+            !0,
+            0x0123_4567_89AB_CDEF_u64,
+            0xDEAD_BEEF_F00D_CAFE,
+            0x8000_0000_0000_0001,
+        ];
+        for &x in &words {
+            for o in 0..=56_u64 {
+                // Eight different offsets per call, all in [0, 56].
+                let offsets = (0..8)
+                    .map(|j| ((o + 7 * j) % 57) << (8 * j))
+                    .fold(0, |a, b| a | b);
+                let expected = (0..8)
+                    .map(|j| {
+                        x.bextr((offsets >> (8 * j)) & 0xFF, 8) << (8 * j)
+                    })
+                    .fold(0, |a, b| a | b);
+                assert_eq!(x.multishift(offsets), expected);
+            }
+            for o in 0..64_u64 {
+                assert_eq!(
+                    x.multishift(o) & 0xFF,
+                    x.rotate_right(o as u32) & 0xFF
+                );
+                assert_eq!(x.multishift(o | 0xC0), x.multishift(o));
+            }
+        }
+    }
+}
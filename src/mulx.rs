@@ -77,6 +77,58 @@ pub trait Mulx: crate::marker::Sized {
     /// }
     /// ```
     fn mulx(self, y: Self) -> (Self, Self);
+
+    /// High half of the product.
+    ///
+    /// Returns the high half of the double-width product of `self` and `y`.
+    /// For unsigned integers this is the `hi` half of
+    /// [`mulx`](#tymethod.mulx). For signed integers the product is signed,
+    /// unlike in `mulx`, which always multiplies the bits of its arguments
+    /// as unsigned integers.
+    ///
+    /// # Instructions
+    ///
+    /// - [`MUL`](http://www.felixcloutier.com/x86/MUL.html),
+    ///   [`IMUL`](http://www.felixcloutier.com/x86/IMUL.html):
+    ///   - Description: Unsigned / signed multiply.
+    ///   - Architecture: x86.
+    ///   - Registers: 8/16/32/64 bit.
+    /// - [`UMULH`](http://infocenter.arm.com/help/index.jsp?topic=/com.arm.doc.
+    ///   ddi0487a.k_10775/index.html), `SMULH`:
+    ///   - Description: Unsigned / signed multiply high.
+    ///   - Architecture: ARMv8.
+    ///   - Registers: 64 bit.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bitintr::*;
+    /// assert_eq!(200_u8.mulhi(200), 156); // 40000 == 0x9C40
+    /// assert_eq!(u64::MAX.mulhi(u64::MAX), u64::MAX - 1);
+    ///
+    /// // -1 * 1 == -1 has all bits set in both halves:
+    /// assert_eq!((-1_i8).mulhi(1), -1);
+    /// // ... while as unsigned 255 * 1 == 0x00FF:
+    /// assert_eq!((-1_i8).mulx(1), (-1, 0));
+    /// assert_eq!(i64::MIN.mulhi(i64::MIN), 1 << 62);
+    /// ```
+    fn mulhi(self, y: Self) -> Self;
+
+    /// Low half of the product.
+    ///
+    /// Returns the low half of the double-width product of `self` and `y`,
+    /// which is the same for signed and unsigned integers, that is, the
+    /// wrapping product.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bitintr::*;
+    /// assert_eq!(200_u8.mullo(200), 0x40);
+    /// assert_eq!((-1_i8).mullo(-1), 1);
+    /// assert_eq!(u64::MAX.mullo(u64::MAX), 1);
+    /// ```
+    fn mullo(self, y: Self) -> Self;
 }
 
 macro_rules! impl_umulx {
@@ -92,6 +144,16 @@ macro_rules! impl_umulx {
                 let hi = (result >> BIT_WIDTH) as Self;
                 (result as Self, hi)
             }
+
+            #[inline]
+            fn mulhi(self, y: Self) -> Self {
+                self.mulx(y).1
+            }
+
+            #[inline]
+            fn mullo(self, y: Self) -> Self {
+                self.wrapping_mul(y)
+            }
         }
     };
 }
@@ -102,7 +164,8 @@ impl_umulx!(u32, u64);
 impl_umulx!(u64, u128);
 
 macro_rules! impl_smulx {
-    ($id:ident, $uid:ident) => {
+    ($id:ident, $uid:ident, $id_l:ident) => {
+        #[allow(clippy::use_self)]
         impl Mulx for $id {
             #[inline]
             fn mulx(self, y: Self) -> (Self, Self) {
@@ -111,11 +174,23 @@ macro_rules! impl_smulx {
                 let (rx, ry) = ux.mulx(uy);
                 (rx as _, ry as _)
             }
+
+            #[inline]
+            fn mulhi(self, y: Self) -> Self {
+                const BIT_WIDTH: u32 =
+                    (crate::mem::size_of::<$id>() * 8) as u32;
+                (((self as $id_l) * (y as $id_l)) >> BIT_WIDTH) as Self
+            }
+
+            #[inline]
+            fn mullo(self, y: Self) -> Self {
+                self.wrapping_mul(y)
+            }
         }
     };
 }
 
-impl_smulx!(i8, u8);
-impl_smulx!(i16, u16);
-impl_smulx!(i32, u32);
-impl_smulx!(i64, u64);
+impl_smulx!(i8, u8, i16);
+impl_smulx!(i16, u16, i32);
+impl_smulx!(i32, u32, i64);
+impl_smulx!(i64, u64, i128);
@@ -0,0 +1,117 @@
+//! parity
+
+/// Parity of the bits set
+pub trait Parity {
+    /// Parity of the bits set.
+    ///
+    /// Returns `true` if the number of bits set in `self` is odd. For slices,
+    /// returns the parity of all bits of all elements.
+    ///
+    /// This is `self.popcnt() & 1`, but it is computed by XOR-folding the
+    /// bits if that is cheaper than counting them, for example on x86 without
+    /// `POPCNT`, where the parity flag gives the parity of the last byte for
+    /// free.
+    ///
+    /// # Instructions
+    ///
+    /// - [`POPCNT`](http://www.felixcloutier.com/x86/POPCNT.html):
+    ///   - Description: Population count.
+    ///   - Architecture: x86.
+    ///   - Instruction set: ABM, SSE4.2.
+    ///   - Registers: 16/32/64 bit.
+    /// - [`SETNP`](http://www.felixcloutier.com/x86/SETcc.html):
+    ///   - Description: Set byte if not parity (odd), after folding the
+    ///     value into a byte.
+    ///   - Architecture: x86.
+    ///   - Registers: 8 bit.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bitintr::*;
+    /// assert!(!0_u8.parity());
+    /// assert!(0b0000_0001_u8.parity());
+    /// assert!(!0b0101_0000_u8.parity());
+    /// assert!(!(-1_i8).parity());
+    /// assert!(0x8000_0000_0000_0000_u64.parity());
+    ///
+    /// assert!(!(&[0b01_u32, 0b10][..]).parity());
+    /// assert!((&[0b01_u32, 0b11][..]).parity());
+    /// ```
+    fn parity(self) -> bool;
+}
+
+cfg_if! {
+    if #[cfg(all(
+        any(target_arch = "x86", target_arch = "x86_64"),
+        target_feature = "popcnt"
+    ))] {
+        #[inline]
+        fn parity_(x: u64) -> bool {
+            x.count_ones() & 1 != 0
+        }
+    } else if #[cfg(any(target_arch = "x86", target_arch = "x86_64"))] {
+        #[inline]
+        fn parity_(mut x: u64) -> bool {
+            x ^= x >> 32;
+            x ^= x >> 16;
+            // The parity of a byte is lowered to `SETNP`:
+            ((x ^ (x >> 8)) as u8).count_ones() & 1 != 0
+        }
+    } else {
+        #[inline]
+        fn parity_(mut x: u64) -> bool {
+            x ^= x >> 32;
+            x ^= x >> 16;
+            x ^= x >> 8;
+            x ^= x >> 4;
+            // Bit `i` of `0x6996` is the parity of the nibble `i`:
+            (0x6996 >> (x & 0xF)) & 1 != 0
+        }
+    }
+}
+
+macro_rules! impl_parity {
+    ($id:ident, $uid:ident) => {
+        impl Parity for $id {
+            #[inline]
+            fn parity(self) -> bool {
+                // The folding steps of the bits that are zero after the
+                // zero-extension are optimized away.
+                parity_(u64::from(self as $uid))
+            }
+        }
+
+        impl<'a> Parity for &'a [$id] {
+            #[inline]
+            fn parity(self) -> bool {
+                self.iter().fold(0, |acc, &x| acc ^ x).parity()
+            }
+        }
+    };
+}
+
+impl_parity!(u8, u8);
+impl_parity!(u16, u16);
+impl_parity!(u32, u32);
+impl_parity!(u64, u64);
+impl_parity!(i8, u8);
+impl_parity!(i16, u16);
+impl_parity!(i32, u32);
+impl_parity!(i64, u64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn popcnt() {
+        for x in 0..=u16::MAX {
+            assert_eq!(x.parity(), x.count_ones() & 1 == 1);
+            assert_eq!((x as i16).parity(), x.count_ones() & 1 == 1);
+            let y = u64::from(x).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+            assert_eq!(y.parity(), y.count_ones() & 1 == 1);
+            assert_eq!((y as u32).parity(), (y as u32).count_ones() & 1 == 1);
+        }
+    }
+}
@@ -19,6 +19,16 @@ pub trait Pdep {
     ///   - Architecture: x86.
     ///   - Instruction set: BMI2.
     ///   - Registers: 32/64 bit.
+    /// - [`BDEP`](https://developer.arm.com/documentation/ddi0602/latest/SVE-Instructions):
+    ///   - Description: Scatter lower bits into positions selected by bitmask.
+    ///   - Architecture: AArch64.
+    ///   - Instruction set: SVE2 (BITPERM).
+    ///   - Registers: 8/16/32/64 bit vector lanes.
+    /// - [`PDEPD`](https://openpowerfoundation.org/specifications/isa/):
+    ///   - Description: Parallel bits deposit doubleword.
+    ///   - Architecture: PowerPC.
+    ///   - Instruction set: Power ISA 3.1.
+    ///   - Registers: 64 bit.
     ///
     /// # Example
     ///
@@ -51,12 +61,18 @@ macro_rules! pdep_impl {
                 if (value & bb) != 0 {
                     res |= mask & mask.wrapping_neg();
                 }
-                mask &= mask - 1;
+                mask &= mask.wrapping_sub(1);
                 bb = bb.wrapping_add(bb);
             }
             res
         }
     };
+    ($ty:ty, $instr:literal) => {
+        #[inline]
+        fn pdep_(value: $ty, mask: $ty) -> $ty {
+            power10_asm!($instr, $ty, value, mask)
+        }
+    };
     ($ty:ty, $intr:ident) => {
         cfg_if! {
             if  #[cfg(all(
@@ -80,7 +96,7 @@ macro_rules! pdep_impl {
 }
 
 macro_rules! impl_pdep {
-    ($id:ident $(,$args:ident)*) => {
+    ($id:ident $(,$args:tt)*) => {
         impl Pdep for $id {
             #[inline]
             #[allow(unused_unsafe)]
@@ -110,6 +126,15 @@ cfg_if! {
                 impl_all!(impl_pdep: i64, u64);
             }
         }
+    } else if #[cfg(all(
+        target_arch = "powerpc64",
+        target_feature = "power10-vector",
+        bitintr_nightly
+    ))] {
+        impl_pdep!(u32, "pdepd");
+        impl_pdep!(i32, "pdepd");
+        impl_pdep!(u64, "pdepd");
+        impl_pdep!(i64, "pdepd");
     } else {
         impl_all!(impl_pdep: u32, i32, i64, u64);
     }
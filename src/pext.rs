@@ -18,6 +18,16 @@ pub trait Pext {
     ///   - Architecture: x86.
     ///   - Instruction set: BMI2.
     ///   - Registers: 32/64 bit.
+    /// - [`BEXT`](https://developer.arm.com/documentation/ddi0602/latest/SVE-Instructions):
+    ///   - Description: Gather lower bits from positions selected by bitmask.
+    ///   - Architecture: AArch64.
+    ///   - Instruction set: SVE2 (BITPERM).
+    ///   - Registers: 8/16/32/64 bit vector lanes.
+    /// - [`PEXTD`](https://openpowerfoundation.org/specifications/isa/):
+    ///   - Description: Parallel bits extract doubleword.
+    ///   - Architecture: PowerPC.
+    ///   - Instruction set: Power ISA 3.1.
+    ///   - Registers: 64 bit.
     ///
     /// # Example
     ///
@@ -50,12 +60,18 @@ macro_rules! pext_impl {
                 if value & mask & (mask.wrapping_neg()) != 0 {
                     res |= bb;
                 }
-                mask &= mask - 1;
+                mask &= mask.wrapping_sub(1);
                 bb = bb.wrapping_add(bb);
             }
             res
         }
     };
+    ($ty:ty, $instr:literal) => {
+        #[inline]
+        fn pext_(value: $ty, mask: $ty) -> $ty {
+            power10_asm!($instr, $ty, value, mask)
+        }
+    };
     ($ty:ty, $intr:ident) => {
         cfg_if! {
             if  #[cfg(all(
@@ -78,7 +94,7 @@ macro_rules! pext_impl {
 }
 
 macro_rules! impl_pext {
-    ($id:ident $(,$args:ident)*) => {
+    ($id:ident $(,$args:tt)*) => {
         impl Pext for $id {
             #[inline]
             #[allow(unused_unsafe)]
@@ -108,6 +124,15 @@ cfg_if! {
                 impl_all!(impl_pext: i64, u64);
             }
         }
+    } else if #[cfg(all(
+        target_arch = "powerpc64",
+        target_feature = "power10-vector",
+        bitintr_nightly
+    ))] {
+        impl_pext!(u32, "pextd");
+        impl_pext!(i32, "pextd");
+        impl_pext!(u64, "pextd");
+        impl_pext!(i64, "pextd");
     } else {
         impl_all!(impl_pext: u32, i32, i64, u64);
     }
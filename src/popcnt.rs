@@ -15,7 +15,7 @@ pub trait Popcnt {
     ///   - Instruction set: ABM, SSE 4.2.
     ///   - Registers: 16/32/64 bit.
     /// - Note: Intel considers it part of SSE4.2 but advertises it with its
-    /// own   CPUID flag.
+    ///   own CPUID flag.
     ///
     /// # Example
     /// ```
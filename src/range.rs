@@ -0,0 +1,122 @@
+//! Range reduction without division.
+//!
+//! These functions map integers that are uniformly distributed over the
+//! whole range of a word, like hashes or the output of a random number
+//! generator, into the range `[0, n)` using the high half of a
+//! multiplication (see [`Mulx::mulhi`](../trait.Mulx.html#tymethod.mulhi))
+//! instead of a division.
+//!
+//! See D. Lemire, "Fast Random Integer Generation in an Interval".
+
+use crate::Mulx;
+
+/// Maps `hash` into the range `[0, n)`.
+///
+/// Computes `floor(hash * n / 2^64)`, which is a fair map from `[0, 2^64)`
+/// into `[0, n)` that, unlike `hash % n`, depends mostly on the high bits
+/// of `hash`. Returns `0` if `n == 0`.
+///
+/// # Example
+///
+/// ```
+/// # use bitintr::range::*;
+/// assert_eq!(fastrange(0, 10), 0);
+/// assert_eq!(fastrange(u64::MAX, 10), 9);
+/// assert_eq!(fastrange(1 << 63, 10), 5);
+/// ```
+#[inline]
+pub fn fastrange(hash: u64, n: u64) -> u64 {
+    hash.mulhi(n)
+}
+
+/// Maps `hash` into the range `[0, n)`.
+///
+/// Like [`fastrange`](fn.fastrange.html), but for 32-bit hashes.
+///
+/// # Example
+///
+/// ```
+/// # use bitintr::range::*;
+/// assert_eq!(fastrange32(u32::MAX, 10), 9);
+/// assert_eq!(fastrange32(1 << 31, 10), 5);
+/// ```
+#[inline]
+pub fn fastrange32(hash: u32, n: u32) -> u32 {
+    hash.mulhi(n)
+}
+
+/// Generates an unbiased random integer in the range `[0, n)`.
+///
+/// Maps the output of the uniform random number generator `rng` into
+/// `[0, n)` with [`fastrange`](fn.fastrange.html), rejecting the outputs
+/// that would bias the result. A division is only computed when the first
+/// output of `rng` lands in the biased region, which happens with
+/// probability `n / 2^64`. Returns `0` if `n == 0`.
+///
+/// # Example
+///
+/// ```
+/// # use bitintr::range::*;
+/// let mut x = 0x0123_4567_89AB_CDEF_u64;
+/// let mut xorshift64 = || {
+///     x ^= x << 13;
+///     x ^= x >> 7;
+///     x ^= x << 17;
+///     x
+/// };
+/// for _ in 0..100 {
+///     let die = bounded_random(6, &mut xorshift64) + 1;
+///     assert!(1 <= die && die <= 6);
+/// }
+/// ```
+#[inline]
+pub fn bounded_random<F: FnMut() -> u64>(n: u64, mut rng: F) -> u64 {
+    let (mut lo, mut hi) = rng().mulx(n);
+    if lo < n {
+        // Outputs whose low half is below `2^64 mod n` are rejected, which
+        // leaves exactly `floor(2^64 / n)` outputs for each result.
+        let threshold = n.wrapping_neg() % n;
+        while lo < threshold {
+            let (l, h) = rng().mulx(n);
+            lo = l;
+            hi = h;
+        }
+    }
+    hi
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::xorshift64;
+
+    // Rejects `x` if `x * n mod 2^64 < 2^64 mod n`, using `u128` arithmetic.
+    fn bounded_random_naive<F: FnMut() -> u64>(n: u64, mut rng: F) -> u64 {
+        let threshold = ((1_u128 << 64) % u128::from(n)) as u64;
+        loop {
+            let p = u128::from(rng()) * u128::from(n);
+            if p as u64 >= threshold {
+                return (p >> 64) as u64;
+            }
+        }
+    }
+
+    #[test]
+    fn bounded_random_rejection() {
+        // For `n > 2^63` about `(2^64 - n) / 2^64` of the outputs are
+        // rejected.
+        let ns = [1, 3, 6, 1 << 32, (1 << 63) + 1, (3 << 62) + 7, u64::MAX];
+        for &n in &ns {
+            let (mut a, mut b) = (xorshift64(n | 1), xorshift64(n | 1));
+            for _ in 0..1000 {
+                let r = bounded_random(n, &mut a);
+                assert!(r < n);
+                assert_eq!(r, bounded_random_naive(n, &mut b));
+            }
+        }
+        // 0 is rejected for every `n` that is not a power of two:
+        let mut seq = [u64::MAX, 0].iter().rev().cloned();
+        assert_eq!(bounded_random(3, || seq.next().unwrap()), 2);
+        assert_eq!(bounded_random(0, || u64::MAX), 0);
+    }
+}
@@ -0,0 +1,159 @@
+//! rank
+
+/// Count bits set below a bit position.
+pub trait Rank {
+    /// Counts the bits set of `self` at positions below `i`.
+    ///
+    /// This is `self.bzhi(i).popcnt()`, but it is also defined for `i` equal
+    /// to the bit width of `self`, where it is `self.popcnt()`. For the
+    /// positions `i` of the bits set it is the inverse of
+    /// [`Select::select`](trait.Select.html#tymethod.select), that is,
+    /// `x.rank(x.select(k).unwrap()) == k`.
+    ///
+    /// **Keywords**: Rank, population count of a prefix.
+    ///
+    /// # Panics
+    ///
+    /// If `i > bit_size()` and `-C debug-assertions=1`.
+    ///
+    /// # Instructions
+    ///
+    /// - [`BZHI`](http://www.felixcloutier.com/x86/BZHI.html):
+    ///   - Description: Zero high bits starting with specified bit position.
+    ///   - Architecture: x86.
+    ///   - Instruction set: BMI2.
+    ///   - Registers: 32/64 bit.
+    /// - [`POPCNT`](http://www.felixcloutier.com/x86/POPCNT.html):
+    ///   - Description: Population Count.
+    ///   - Architecture: x86.
+    ///   - Instruction set: ABM, SSE 4.2.
+    ///   - Registers: 16/32/64 bit.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bitintr::*;
+    /// assert_eq!(0b1010_0100_u8.rank(0), 0);
+    /// assert_eq!(0b1010_0100_u8.rank(3), 1);
+    /// assert_eq!(0b1010_0100_u8.rank(7), 2);
+    /// assert_eq!(0b1010_0100_u8.rank(8), 3);
+    /// assert_eq!(u64::MAX.rank(64), 64);
+    /// ```
+    fn rank(self, i: u32) -> u32;
+
+    /// Counts the zero bits of `self` at positions below `i`.
+    ///
+    /// This is `i - self.rank(i)`.
+    ///
+    /// # Panics
+    ///
+    /// If `i > bit_size()` and `-C debug-assertions=1`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bitintr::*;
+    /// assert_eq!(0b1010_0100_u8.rank0(3), 2);
+    /// assert_eq!(0b1010_0100_u8.rank0(8), 5);
+    /// assert_eq!(0_i64.rank0(64), 64);
+    /// ```
+    fn rank0(self, i: u32) -> u32;
+}
+
+cfg_if! {
+    if #[cfg(all(target_arch = "x86_64", target_feature = "bmi2"))] {
+        #[inline]
+        #[target_feature(enable = "bmi2")]
+        unsafe fn rank_(x: u64, i: u32) -> u32 {
+            // `BZHI` leaves `x` unchanged if `i >= 64`:
+            crate::arch::_bzhi_u64(x, i).count_ones()
+        }
+    } else {
+        #[inline]
+        fn rank_(x: u64, i: u32) -> u32 {
+            use crate::Bzhi;
+            if i < 64 {
+                x.bzhi(i).count_ones()
+            } else {
+                x.count_ones()
+            }
+        }
+    }
+}
+
+macro_rules! impl_rank {
+    ($id:ident, $uid:ident) => {
+        impl Rank for $id {
+            #[inline]
+            #[allow(unused_unsafe)]
+            fn rank(self, i: u32) -> u32 {
+                debug_assert!(i <= (crate::mem::size_of::<$id>() * 8) as u32);
+                // UNSAFETY: this is always safe, because
+                // the unsafe `#[target_feature]` function
+                // is only generated when the feature is
+                // statically-enabled at compile-time.
+                unsafe { rank_(u64::from(self as $uid), i) }
+            }
+            #[inline]
+            fn rank0(self, i: u32) -> u32 {
+                i - self.rank(i)
+            }
+        }
+    };
+}
+
+impl_rank!(u8, u8);
+impl_rank!(u16, u16);
+impl_rank!(u32, u32);
+impl_rank!(u64, u64);
+impl_rank!(i8, u8);
+impl_rank!(i16, u16);
+impl_rank!(i32, u32);
+impl_rank!(i64, u64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{xorshift64, SEED};
+    use crate::Select;
+
+    fn rank_naive(x: u64, i: u32) -> u32 {
+        (0..i).filter(|&j| x & (1 << j) != 0).count() as u32
+    }
+
+    #[test]
+    fn rank() {
+        for x in 0..=u16::MAX {
+            for i in 0..=16 {
+                let r = rank_naive(u64::from(x), i);
+                assert_eq!(x.rank(i), r);
+                assert_eq!((x as i16).rank(i), r);
+                assert_eq!((!x).rank0(i), r);
+            }
+            for i in 0..=8 {
+                assert_eq!(
+                    (x as u8).rank(i),
+                    rank_naive(u64::from(x as u8), i)
+                );
+            }
+        }
+        let mut next = xorshift64(SEED);
+        for _ in 0..1000 {
+            let x = next();
+            for i in 0..=64 {
+                assert_eq!(x.rank(i), rank_naive(x, i));
+                assert_eq!(x.rank0(i), i - rank_naive(x, i));
+                if i <= 32 {
+                    let y = x as u32;
+                    assert_eq!(y.rank(i), rank_naive(u64::from(y), i));
+                }
+            }
+            for k in 0..x.count_ones() {
+                assert_eq!(x.rank(x.select(k).unwrap()), k);
+            }
+            for k in 0..x.count_zeros() {
+                assert_eq!(x.rank0(x.select0(k).unwrap()), k);
+            }
+        }
+    }
+}
@@ -126,7 +126,7 @@ mod tests {
 
     #[test]
     fn rbit_u8() {
-        (0..u8::max_value())
+        (0..u8::MAX)
             .map(|x| {
                 assert_eq!(x, x.rbit().rbit());
                 let x = x as i8;
@@ -136,7 +136,7 @@ mod tests {
     }
     #[test]
     fn rbit_u16() {
-        (0..u16::max_value())
+        (0..u16::MAX)
             .map(|x| {
                 assert_eq!(x, x.rbit().rbit());
 
@@ -147,7 +147,7 @@ mod tests {
     }
     #[test]
     fn rbit_u32() {
-        (0..u32::max_value())
+        (0..u32::MAX)
             .take(1000000)
             .map(|x| {
                 assert_eq!(x, x.rbit().rbit());
@@ -158,7 +158,7 @@ mod tests {
     }
     #[test]
     fn rbit_u64() {
-        (0..u64::max_value())
+        (0..u64::MAX)
             .take(1000000)
             .map(|x| {
                 assert_eq!(x, x.rbit().rbit());
@@ -0,0 +1,86 @@
+//! rorx
+
+/// Rotate right without affecting flags
+pub trait Rorx {
+    /// Rotate right logical without affecting flags.
+    ///
+    /// Rotates the bits of `self` right by `n` bits, shifting the bits
+    /// truncated at the least significant end into the most significant end.
+    ///
+    /// Only `n % bit_size()` is used, so every `n` is valid.
+    ///
+    /// **Keywords**: Rotate right, circular shift.
+    ///
+    /// # Instructions
+    ///
+    /// - [`RORX`](http://www.felixcloutier.com/x86/RORX.html):
+    ///   - Description: Rotate right logical without affecting flags.
+    ///   - Architecture: x86.
+    ///   - Instruction set: BMI2.
+    ///   - Registers: 32/64 bit.
+    /// - [`ROR`](http://infocenter.arm.com/help/index.jsp?topic=/com.arm.doc.
+    ///   ddi0487a.k_10775/index.html):
+    ///   - Description: Rotate right.
+    ///   - Architecture: ARMv7, ARMv8.
+    ///   - Registers: 32 (v7) / 64 (v8) bits.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bitintr::*;
+    /// assert_eq!(0b0011_0001_u8.rorx(1), 0b1001_1000_u8);
+    /// assert_eq!(0b0011_0001_u8.rorx(9), 0b1001_1000_u8);
+    /// assert_eq!(0x0123_4567_u32.rorx(8), 0x6701_2345_u32);
+    /// ```
+    fn rorx(self, n: u32) -> Self;
+}
+
+macro_rules! impl_rorx {
+    ($id:ident) => {
+        impl Rorx for $id {
+            #[inline]
+            fn rorx(self, n: u32) -> Self {
+                self.rotate_right(n)
+            }
+        }
+    };
+}
+
+impl_all!(impl_rorx: u8, u16, u32, u64, i8, i16, i32, i64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{xorshift64, SEED};
+
+    fn check(x: u64, n: u32) {
+        let r = ((u128::from(x) << 64 | u128::from(x)) >> (n % 64)) as u64;
+        assert_eq!(x.rorx(n), r, "{} {}", x, n);
+        assert_eq!((x as i64).rorx(n), r as i64);
+        let x = x as u32;
+        let r = ((u64::from(x) << 32 | u64::from(x)) >> (n % 32)) as u32;
+        assert_eq!(x.rorx(n), r, "{} {}", x, n);
+        assert_eq!((x as i32).rorx(n), r as i32);
+        let x = x as u16;
+        let r = ((u32::from(x) << 16 | u32::from(x)) >> (n % 16)) as u16;
+        assert_eq!(x.rorx(n), r, "{} {}", x, n);
+        assert_eq!((x as i16).rorx(n), r as i16);
+    }
+
+    #[test]
+    fn rorx() {
+        for x in 0..=u8::MAX {
+            for n in 0..=16 {
+                let r = ((u16::from(x) << 8 | u16::from(x)) >> (n % 8)) as u8;
+                assert_eq!(x.rorx(n), r, "{} {}", x, n);
+                assert_eq!((x as i8).rorx(n), r as i8);
+            }
+        }
+        let mut next = xorshift64(SEED);
+        for _ in 0..10_000 {
+            let (x, n) = (next(), next());
+            check(x, n as u32);
+            check(x, n as u32 % 64);
+        }
+    }
+}
@@ -0,0 +1,120 @@
+//! sarx
+
+/// Shift arithmetic right without affecting flags
+pub trait Sarx {
+    /// Shift arithmetic right without affecting flags.
+    ///
+    /// Shifts `self` right by `n` bits, shifting in copies of the sign bit,
+    /// also for unsigned integers.
+    ///
+    /// The shift count is masked like on x86, on every target: 8 and 16-bit
+    /// integers are shifted as 32-bit integers, so the count is `n % 32` for
+    /// 8, 16 and 32-bit integers, and `n % 64` for 64-bit integers. In
+    /// particular, 8 and 16-bit integers are shifted by the counts in
+    /// `bit_size()..32` instead of masking them to `log2(bit_size())` bits.
+    ///
+    /// # Instructions
+    ///
+    /// - [`SARX`](http://www.felixcloutier.com/x86/SARX:SHLX:SHRX.html):
+    ///   - Description: Shift arithmetic right without affecting flags.
+    ///   - Architecture: x86.
+    ///   - Instruction set: BMI2.
+    ///   - Registers: 32/64 bit.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bitintr::*;
+    /// assert_eq!(0b1100_0000_u8.sarx(2), 0b1111_0000_u8);
+    /// assert_eq!((-128_i8).sarx(2), -32_i8);
+    /// // The count is masked to 5 bits for 8-bit integers:
+    /// assert_eq!((-128_i8).sarx(10), -1_i8);
+    /// assert_eq!((-128_i8).sarx(34), -32_i8);
+    /// ```
+    fn sarx(self, n: u32) -> Self;
+
+    /// Saturating shift arithmetic right.
+    ///
+    /// Like [`sarx`](#tymethod.sarx), but shift counts `n >= bit_size()`
+    /// produce a copy of the sign bit in every bit instead of being masked.
+    /// This matches the semantics of register-specified shifts on 32-bit ARM.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bitintr::*;
+    /// assert_eq!((-128_i8).sarx_sat(2), -32_i8);
+    /// assert_eq!((-128_i8).sarx_sat(10), -1_i8);
+    /// assert_eq!(0b0100_0000_u8.sarx_sat(10), 0);
+    /// ```
+    fn sarx_sat(self, n: u32) -> Self;
+}
+
+macro_rules! impl_sarx {
+    ($id:ident, $sid:ident, $wid:ident) => {
+        #[allow(clippy::use_self)]
+        impl Sarx for $id {
+            #[inline]
+            fn sarx(self, n: u32) -> Self {
+                // `$wid` is the type of the x86 operand, to which `self`
+                // is sign-extended:
+                (self as $sid as $wid).wrapping_shr(n) as Self
+            }
+
+            #[inline]
+            fn sarx_sat(self, n: u32) -> Self {
+                const BIT_WIDTH: u32 =
+                    (crate::mem::size_of::<$id>() * 8) as u32;
+                (self as $sid).wrapping_shr(n.min(BIT_WIDTH - 1)) as Self
+            }
+        }
+    };
+}
+
+impl_sarx!(u8, i8, i32);
+impl_sarx!(i8, i8, i32);
+impl_sarx!(u16, i16, i32);
+impl_sarx!(i16, i16, i32);
+impl_sarx!(u32, i32, i32);
+impl_sarx!(i32, i32, i32);
+impl_sarx!(u64, i64, i64);
+impl_sarx!(i64, i64, i64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{xorshift64, SEED};
+
+    macro_rules! check {
+        ($x:expr, $id:ident, $sid:ident, $mask:expr) => {{
+            let x = $x as $id;
+            let sx = x as $sid;
+            let fill = if sx < 0 { -1 } else { 0 };
+            for n in 0..=255 {
+                let bits = (crate::mem::size_of::<$id>() * 8) as u32;
+                let expected = sx.checked_shr(n & $mask).unwrap_or(fill);
+                assert_eq!(x.sarx(n), expected as $id, "{} {}", x, n);
+                let sat = if n < bits { sx.wrapping_shr(n) } else { fill };
+                assert_eq!(x.sarx_sat(n), sat as $id, "{} {}", x, n);
+            }
+        }};
+    }
+
+    #[test]
+    fn sarx() {
+        for x in 0..=u8::MAX {
+            check!(x, u8, i8, 31);
+            check!(x, i8, i8, 31);
+        }
+        let mut next = xorshift64(SEED);
+        for _ in 0..256 {
+            let x = next();
+            check!(x, u16, i16, 31);
+            check!(x, i16, i16, 31);
+            check!(x, u32, i32, 31);
+            check!(x, i32, i32, 31);
+            check!(x, u64, i64, 63);
+            check!(x, i64, i64, 63);
+        }
+    }
+}
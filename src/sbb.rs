@@ -0,0 +1,159 @@
+//! sbb
+
+/// Unsigned subtract with borrow.
+pub trait Sbb: crate::marker::Sized {
+    /// Unsigned subtract with borrow.
+    ///
+    /// Subtracts `y` and `borrow_in` from `self`, returning a pair
+    /// `(difference, borrow_out)` with the wrapped difference and whether the
+    /// subtraction underflowed.
+    ///
+    /// Chaining the borrow through a sequence of subtractions computes the
+    /// difference of multi-word integers.
+    ///
+    /// # Instructions
+    ///
+    /// - [`SBB`](http://www.felixcloutier.com/x86/SBB.html):
+    ///   - Description: Integer subtraction with borrow.
+    ///   - Architecture: x86.
+    ///   - Registers: 8/16/32/64 bit.
+    /// - [`SBCS`](https://developer.arm.com/documentation/ddi0602/latest/Base-Instructions/SBCS--Subtract-with-carry--setting-flags-):
+    ///   - Description: Subtract with carry, setting flags.
+    ///   - Architecture: AArch64.
+    ///   - Registers: 32/64 bit.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bitintr::*;
+    /// assert_eq!(200_u8.sbb(50, false), (150, false));
+    /// assert_eq!(0_u8.sbb(0, true), (255, true));
+    /// assert_eq!(0_u64.sbb(u64::MAX, true), (0, true));
+    ///
+    /// // 128-bit subtraction with two 64-bit words:
+    /// let (a_lo, a_hi) = (0_u64, 4_u64);
+    /// let (b_lo, b_hi) = (1_u64, 2_u64);
+    /// let (lo, b) = a_lo.sbb(b_lo, false);
+    /// let (hi, b) = a_hi.sbb(b_hi, b);
+    /// assert_eq!((lo, hi, b), (u64::MAX, 1, false));
+    /// ```
+    fn sbb(self, y: Self, borrow_in: bool) -> (Self, bool);
+}
+
+macro_rules! sbb_impl {
+    ($ty:ty) => {
+        #[inline]
+        fn sbb_(x: $ty, y: $ty, borrow_in: bool) -> ($ty, bool) {
+            let (d, b0) = x.overflowing_sub(y);
+            let (d, b1) = d.overflowing_sub(borrow_in as $ty);
+            (d, b0 | b1)
+        }
+    };
+    ($ty:ty, $sbcs:literal) => {
+        #[inline]
+        fn sbb_(x: $ty, y: $ty, borrow_in: bool) -> ($ty, bool) {
+            let (mut d, mut b) = (x, borrow_in as u32);
+            // The carry flag of AArch64 is the negated borrow: `CMP` clears
+            // it iff `b == 1`, and `CSET` reads it back after `SBCS`.
+            unsafe {
+                core::arch::asm!(
+                    "cmp wzr, {b:w}",
+                    $sbcs,
+                    "cset {b:w}, lo",
+                    d = inout(reg) d,
+                    y = in(reg) y,
+                    b = inout(reg) b,
+                    options(pure, nomem, nostack),
+                );
+            }
+            (d, b != 0)
+        }
+    };
+    ($ty:ty, $intr:ident) => {
+        #[inline]
+        fn sbb_(x: $ty, y: $ty, borrow_in: bool) -> ($ty, bool) {
+            let mut d = 0;
+            #[allow(unused_unsafe)]
+            let b =
+                unsafe { crate::arch::$intr(borrow_in as u8, x, y, &mut d) };
+            (d, b != 0)
+        }
+    };
+}
+
+macro_rules! impl_sbb {
+    ($id:ident $(,$args:tt)*) => {
+        impl Sbb for $id {
+            #[inline]
+            fn sbb(self, y: Self, borrow_in: bool) -> (Self, bool) {
+                sbb_impl!($id $(,$args)*);
+                sbb_(self, y, borrow_in)
+            }
+        }
+    };
+}
+
+impl_all!(impl_sbb: u8, u16);
+
+cfg_if! {
+    if #[cfg(any(target_arch = "x86", target_arch = "x86_64"))] {
+        impl_sbb!(u32, _subborrow_u32);
+        cfg_if! {
+            if #[cfg(target_arch = "x86_64")] {
+                impl_sbb!(u64, _subborrow_u64);
+            } else {
+                impl_all!(impl_sbb: u64);
+            }
+        }
+    } else if #[cfg(target_arch = "aarch64")] {
+        impl_sbb!(u32, "sbcs {d:w}, {d:w}, {y:w}");
+        impl_sbb!(u64, "sbcs {d:x}, {d:x}, {y:x}");
+    } else {
+        impl_all!(impl_sbb: u32, u64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{xorshift64, SEED};
+
+    fn check(x: u64, y: u64, b: bool) {
+        let d = u128::from(x)
+            .wrapping_sub(u128::from(y))
+            .wrapping_sub(u128::from(b));
+        assert_eq!(x.sbb(y, b), (d as u64, d >> 64 != 0), "{} {} {}", x, y, b);
+        let (x, y) = (x as u32, y as u32);
+        let d = u64::from(x)
+            .wrapping_sub(u64::from(y))
+            .wrapping_sub(u64::from(b));
+        assert_eq!(x.sbb(y, b), (d as u32, d >> 32 != 0), "{} {} {}", x, y, b);
+        let (x, y) = (x as u16, y as u16);
+        let d = i32::from(x) - i32::from(y) - i32::from(b);
+        assert_eq!(x.sbb(y, b), (d as u16, d < 0), "{} {} {}", x, y, b);
+    }
+
+    #[test]
+    fn sbb() {
+        for x in 0..=u8::MAX {
+            for y in 0..=u8::MAX {
+                for &b in &[false, true] {
+                    let d = i16::from(x) - i16::from(y) - i16::from(b);
+                    assert_eq!(x.sbb(y, b), (d as u8, d < 0));
+                }
+            }
+        }
+        let mut next = xorshift64(SEED);
+        for _ in 0..10_000 {
+            let (x, y) = (next(), next());
+            check(x, y, false);
+            check(x, y, true);
+        }
+        for &x in &[0, 1, u64::MAX - 1, u64::MAX] {
+            for &y in &[0, 1, u64::MAX - 1, u64::MAX] {
+                check(x, y, false);
+                check(x, y, true);
+            }
+        }
+    }
+}
@@ -0,0 +1,252 @@
+//! select
+
+/// Position of the k-th set bit.
+pub trait Select {
+    /// Position of the `k`-th set bit.
+    ///
+    /// Returns the index of the `k`-th lowest set bit of `self`, counting
+    /// from zero, or `None` if `self` has `k` or fewer bits set. This is the
+    /// inverse of [`Rank::rank`](trait.Rank.html#tymethod.rank).
+    ///
+    /// **Keywords**: Select, find k-th set bit.
+    ///
+    /// # Instructions
+    ///
+    /// - [`PDEP`](http://www.felixcloutier.com/x86/PDEP.html) and
+    ///   [`TZCNT`](http://www.felixcloutier.com/x86/TZCNT.html):
+    ///   - Description: Deposits `1 << k` at the bits set of `self`, and
+    ///     counts the trailing zeros of the result.
+    ///   - Architecture: x86.
+    ///   - Instruction set: BMI2.
+    ///   - Registers: 64 bit.
+    /// - Otherwise, the byte that contains the bit is found with a
+    ///   broadword prefix sum of the byte population counts, and the bit
+    ///   within that byte is looked up in a table.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bitintr::*;
+    /// assert_eq!(0b1010_0100_u8.select(0), Some(2));
+    /// assert_eq!(0b1010_0100_u8.select(1), Some(5));
+    /// assert_eq!(0b1010_0100_u8.select(2), Some(7));
+    /// assert_eq!(0b1010_0100_u8.select(3), None);
+    /// assert_eq!((-1_i64).select(63), Some(63));
+    /// ```
+    fn select(self, k: u32) -> Option<u32>;
+
+    /// Position of the `k`-th zero bit.
+    ///
+    /// Returns the index of the `k`-th lowest zero bit of `self`, counting
+    /// from zero, or `None` if `self` has `k` or fewer zero bits. This is
+    /// `(!self).select(k)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bitintr::*;
+    /// assert_eq!(0b1010_0100_u8.select0(0), Some(0));
+    /// assert_eq!(0b1010_0100_u8.select0(2), Some(3));
+    /// assert_eq!(0b1010_0100_u8.select0(4), Some(6));
+    /// assert_eq!(0b1010_0100_u8.select0(5), None);
+    /// ```
+    fn select0(self, k: u32) -> Option<u32>;
+
+    /// Position of the `k`-th set bit counting from the most significant
+    /// bit.
+    ///
+    /// Returns the index, counting from the least significant bit, of the
+    /// `k`-th highest set bit of `self`, or `None` if `self` has `k` or
+    /// fewer bits set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bitintr::*;
+    /// assert_eq!(0b1010_0100_u8.select_rev(0), Some(7));
+    /// assert_eq!(0b1010_0100_u8.select_rev(1), Some(5));
+    /// assert_eq!(0b1010_0100_u8.select_rev(2), Some(2));
+    /// assert_eq!(0b1010_0100_u8.select_rev(3), None);
+    /// ```
+    fn select_rev(self, k: u32) -> Option<u32>;
+}
+
+/// Index of the `k`-th set bit of `x`, which must have more than `k` bits
+/// set, using a broadword prefix sum of the byte population counts.
+///
+/// See S. Vigna, "Broadword Implementation of Rank/Select Queries".
+#[cfg_attr(
+    all(target_arch = "x86_64", target_feature = "bmi2"),
+    allow(dead_code)
+)]
+#[inline]
+fn select_broadword(x: u64, k: u32) -> u32 {
+    const L8: u64 = 0x0101_0101_0101_0101;
+    const H8: u64 = 0x8080_8080_8080_8080;
+    /// `SELECT_IN_BYTE[b + 256 * k]` is the index of the `k`-th set bit of
+    /// the byte `b`, or `8` if it has `k` or fewer bits set.
+    const SELECT_IN_BYTE: [u8; 256 * 8] = {
+        let mut t = [8; 256 * 8];
+        let mut b = 0;
+        while b < 256 {
+            let mut k = 0;
+            let mut i = 0;
+            while i < 8 {
+                if b & (1 << i) != 0 {
+                    t[b + 256 * k] = i as u8;
+                    k += 1;
+                }
+                i += 1;
+            }
+            b += 1;
+        }
+        t
+    };
+    debug_assert!(k < x.count_ones());
+
+    // Population counts of the bytes:
+    let mut s = x - ((x >> 1) & 0x5555_5555_5555_5555);
+    s = (s & 0x3333_3333_3333_3333) + ((s >> 2) & 0x3333_3333_3333_3333);
+    s = (s + (s >> 4)) & 0x0F0F_0F0F_0F0F_0F0F;
+    // Byte `i` holds the number of bits set in the bytes `0..=i`:
+    let byte_sums = s.wrapping_mul(L8);
+    // The number of bytes whose prefix sum is `<= k` is the index of the
+    // byte that contains the bit, times 8:
+    let le_k = ((u64::from(k) * L8) | H8).wrapping_sub(byte_sums) & H8;
+    let offset = (((le_k >> 7).wrapping_mul(L8) >> 53) & !7) as u32;
+    // Bits set in the bytes below:
+    let below = ((byte_sums << 8) >> offset) as u32 & 0xFF;
+    let byte = (x >> offset) as usize & 0xFF;
+    // `k - below < 8`, the mask elides the bounds check:
+    let k = (k - below) as usize & 7;
+    offset + u32::from(SELECT_IN_BYTE[byte + 256 * k])
+}
+
+cfg_if! {
+    if #[cfg(all(target_arch = "x86_64", target_feature = "bmi2"))] {
+        #[inline]
+        #[target_feature(enable = "bmi2")]
+        unsafe fn select_(x: u64, k: u32) -> Option<u32> {
+            use crate::arch::*;
+            if k >= 64 {
+                return None;
+            }
+            // The deposit is zero if `x` has `k` or fewer bits set:
+            let i = _pdep_u64(1 << k, x).trailing_zeros();
+            if i < 64 {
+                Some(i)
+            } else {
+                None
+            }
+        }
+    } else {
+        #[inline]
+        fn select_(x: u64, k: u32) -> Option<u32> {
+            if k >= x.count_ones() {
+                return None;
+            }
+            Some(select_broadword(x, k))
+        }
+    }
+}
+
+macro_rules! impl_select {
+    ($id:ident, $uid:ident) => {
+        impl Select for $id {
+            #[inline]
+            #[allow(unused_unsafe)]
+            fn select(self, k: u32) -> Option<u32> {
+                // UNSAFETY: this is always safe, because
+                // the unsafe `#[target_feature]` function
+                // is only generated when the feature is
+                // statically-enabled at compile-time.
+                unsafe { select_(u64::from(self as $uid), k) }
+            }
+            #[inline]
+            fn select0(self, k: u32) -> Option<u32> {
+                (!self).select(k)
+            }
+            #[inline]
+            fn select_rev(self, k: u32) -> Option<u32> {
+                let n = self.count_ones();
+                if k >= n {
+                    return None;
+                }
+                self.select(n - 1 - k)
+            }
+        }
+    };
+}
+
+impl_select!(u8, u8);
+impl_select!(u16, u16);
+impl_select!(u32, u32);
+impl_select!(u64, u64);
+impl_select!(i8, u8);
+impl_select!(i16, u16);
+impl_select!(i32, u32);
+impl_select!(i64, u64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{xorshift64, SEED};
+
+    fn select_naive(x: u64, k: u32) -> Option<u32> {
+        (0..64).filter(|&i| x & (1 << i) != 0).nth(k as usize)
+    }
+
+    fn check(x: u64) {
+        for k in 0..=64 {
+            let expected = select_naive(x, k);
+            assert_eq!(x.select(k), expected, "{:#x} {}", x, k);
+            if let Some(i) = expected {
+                assert_eq!(select_broadword(x, k), i, "{:#x} {}", x, k);
+            }
+        }
+    }
+
+    #[test]
+    fn select_u8_u16() {
+        for x in 0..=u16::MAX {
+            for k in 0..=16 {
+                let expected = select_naive(u64::from(x), k);
+                assert_eq!(x.select(k), expected);
+                assert_eq!((x as i16).select(k), expected);
+                assert_eq!((!x).select0(k), expected);
+                let n = x.count_ones();
+                let rev = if k < n {
+                    select_naive(u64::from(x), n - 1 - k)
+                } else {
+                    None
+                };
+                assert_eq!(x.select_rev(k), rev);
+            }
+            for k in 0..=8 {
+                let expected = select_naive(u64::from(x as u8), k);
+                assert_eq!((x as u8).select(k), expected);
+                assert_eq!((x as i8).select(k), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn select_u64() {
+        let mut next = xorshift64(SEED);
+        for _ in 0..2000 {
+            let x = next();
+            check(x);
+            check(x & (x >> 7));
+            let y = x as u32;
+            assert_eq!(y.select(0), (y != 0).then_some(y.trailing_zeros()));
+        }
+        for x in (0..=u16::MAX).step_by(7) {
+            check(u64::from(x) << 48);
+            check(u64::from(x) * 0x0001_0001_0001_0001);
+        }
+        check(u64::MAX);
+        check(1 << 63);
+        assert_eq!(0_u64.select0(63), Some(63));
+        assert_eq!(u64::MAX.select0(0), None);
+    }
+}
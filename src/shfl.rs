@@ -0,0 +1,106 @@
+//! shfl
+
+/// Masks selecting the low side of each delta swap performed by the stages
+/// of the generalized shuffle. The stage swapping groups of `n` bits uses
+/// the `n`-th mask and a shift of `n`.
+pub(crate) const STAGE_MASKS: [u128; 6] = [
+    0x2222_2222_2222_2222_2222_2222_2222_2222,
+    0x0C0C_0C0C_0C0C_0C0C_0C0C_0C0C_0C0C_0C0C,
+    0x00F0_00F0_00F0_00F0_00F0_00F0_00F0_00F0,
+    0x0000_FF00_0000_FF00_0000_FF00_0000_FF00,
+    0x0000_0000_FFFF_0000_0000_0000_FFFF_0000,
+    0x0000_0000_0000_0000_FFFF_FFFF_0000_0000,
+];
+
+/// Generalized shuffle
+pub trait Shfl {
+    /// Generalized shuffle.
+    ///
+    /// Performs the stages of a perfect outer shuffle selected by the control
+    /// word `k`, from the most significant stage to the least significant
+    /// one. Only the low `log2(bit_size()) - 1` bits of `k` are used.
+    ///
+    /// With all control bits set (`k == bit_size() / 2 - 1`) this interleaves
+    /// the low half of `self` into the even bits of the result and the high
+    /// half into the odd bits. Clearing the low control bits interleaves
+    /// groups of 2, 4, ... bits instead, and applying the full shuffle
+    /// repeatedly yields 4- and 8-way bit interleaves.
+    ///
+    /// The inverse operation is [`Unshfl`](trait.Unshfl.html). Both are
+    /// implemented for the 16- to 128-bit integer types.
+    ///
+    /// **Keywords**: Generalized shuffle, perfect shuffle, interleave bits,
+    /// zip.
+    ///
+    /// # Instructions
+    ///
+    /// - [`SHFL`](https://github.com/riscv/riscv-bitmanip):
+    ///   - Description: Generalized shuffle.
+    ///   - Architecture: RISC-V.
+    ///   - Instruction set: Zbp (draft).
+    ///   - Registers: 32/64 bit.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bitintr::*;
+    /// assert_eq!(0b0000_0000_1111_1111u16.shfl(7), 0b0101_0101_0101_0101u16);
+    /// assert_eq!(0b1111_1111_0000_0000u16.shfl(7), 0b1010_1010_1010_1010u16);
+    /// assert_eq!(0b1111_1111_0000_0000u16.shfl(6), 0b1100_1100_1100_1100u16);
+    /// assert_eq!(0b1111_1111_0000_0000u16.shfl(4), 0b1111_0000_1111_0000u16);
+    /// ```
+    fn shfl(self, k: u32) -> Self;
+}
+
+macro_rules! impl_shfl {
+    ($id:ident, $uid:ident) => {
+        #[allow(clippy::use_self)]
+        impl Shfl for $id {
+            #[inline]
+            fn shfl(self, k: u32) -> Self {
+                const BIT_WIDTH: u32 =
+                    (crate::mem::size_of::<$id>() * 8) as u32;
+                let k = k & (BIT_WIDTH / 2 - 1);
+                let mut x = self as $uid;
+                for (i, &m) in STAGE_MASKS.iter().enumerate().rev() {
+                    let s = 1 << i;
+                    if k & s != 0 {
+                        let t = ((x >> s) ^ x) & (m as $uid);
+                        x ^= t ^ (t << s);
+                    }
+                }
+                x as Self
+            }
+        }
+    };
+}
+
+impl_shfl!(u16, u16);
+impl_shfl!(i16, u16);
+impl_shfl!(u32, u32);
+impl_shfl!(i32, u32);
+impl_shfl!(u64, u64);
+impl_shfl!(i64, u64);
+impl_shfl!(u128, u128);
+impl_shfl!(i128, u128);
+
+#[cfg(test)]
+mod tests {
+    use crate::Shfl;
+
+    #[test]
+    fn shfl_zip() {
+        // The full shuffle interleaves the low and high halves bit by bit.
+        fn zip(x: u128, width: u32) -> u128 {
+            let half = width / 2;
+            (0..half).fold(0, |r, i| {
+                r | (((x >> i) & 1) << (2 * i))
+                    | (((x >> (i + half)) & 1) << (2 * i + 1))
+            })
+        }
+        let v = 0x0123_4567_89AB_CDEF_FEDC_BA98_7654_3210_u128;
+        assert_eq!((v as u32).shfl(15) as u128, zip(v as u32 as u128, 32));
+        assert_eq!((v as u64).shfl(31) as u128, zip(v as u64 as u128, 64));
+        assert_eq!(v.shfl(63), zip(v, 128));
+    }
+}
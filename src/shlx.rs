@@ -0,0 +1,114 @@
+//! shlx
+
+/// Shift logical left without affecting flags
+pub trait Shlx {
+    /// Shift logical left without affecting flags.
+    ///
+    /// Shifts `self` left by `n` bits, shifting in zeros.
+    ///
+    /// The shift count is masked like on x86, on every target: 8 and 16-bit
+    /// integers are shifted as 32-bit integers, so the count is `n % 32` for
+    /// 8, 16 and 32-bit integers, and `n % 64` for 64-bit integers. In
+    /// particular, 8 and 16-bit integers are shifted by the counts in
+    /// `bit_size()..32` instead of masking them to `log2(bit_size())` bits.
+    ///
+    /// # Instructions
+    ///
+    /// - [`SHLX`](http://www.felixcloutier.com/x86/SARX:SHLX:SHRX.html):
+    ///   - Description: Shift logical left without affecting flags.
+    ///   - Architecture: x86.
+    ///   - Instruction set: BMI2.
+    ///   - Registers: 32/64 bit.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bitintr::*;
+    /// assert_eq!(0b0000_0011_u8.shlx(2), 0b0000_1100_u8);
+    /// // The count is masked to 5 bits for 8-bit integers:
+    /// assert_eq!(0b0000_0011_u8.shlx(10), 0);
+    /// assert_eq!(0b0000_0011_u8.shlx(34), 0b0000_1100_u8);
+    /// assert_eq!(0x1234_5678_u32.shlx(36), 0x2345_6780_u32);
+    /// ```
+    fn shlx(self, n: u32) -> Self;
+
+    /// Saturating shift logical left.
+    ///
+    /// Like [`shlx`](#tymethod.shlx), but shift counts `n >= bit_size()`
+    /// produce 0 instead of being masked. This matches the semantics of
+    /// register-specified shifts on 32-bit ARM.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bitintr::*;
+    /// assert_eq!(0b0000_0011_u8.shlx_sat(2), 0b0000_1100_u8);
+    /// assert_eq!(0b0000_0011_u8.shlx_sat(10), 0);
+    /// assert_eq!(0x1234_5678_u32.shlx_sat(36), 0);
+    /// ```
+    fn shlx_sat(self, n: u32) -> Self;
+}
+
+macro_rules! impl_shlx {
+    ($id:ident, $wid:ident) => {
+        #[allow(clippy::use_self)]
+        impl Shlx for $id {
+            #[inline]
+            fn shlx(self, n: u32) -> Self {
+                // `$wid` is the type of the x86 operand:
+                (self as $wid).wrapping_shl(n) as Self
+            }
+
+            #[inline]
+            fn shlx_sat(self, n: u32) -> Self {
+                self.checked_shl(n).unwrap_or(0)
+            }
+        }
+    };
+}
+
+impl_shlx!(u8, u32);
+impl_shlx!(i8, u32);
+impl_shlx!(u16, u32);
+impl_shlx!(i16, u32);
+impl_shlx!(u32, u32);
+impl_shlx!(i32, u32);
+impl_shlx!(u64, u64);
+impl_shlx!(i64, u64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{xorshift64, SEED};
+
+    macro_rules! check {
+        ($x:expr, $id:ident, $mask:expr) => {{
+            let x = $x as $id;
+            for n in 0..=255 {
+                let bits = (crate::mem::size_of::<$id>() * 8) as u32;
+                let expected = x.checked_shl(n & $mask).unwrap_or(0);
+                assert_eq!(x.shlx(n), expected, "{} {}", x, n);
+                let sat = if n < bits { x.wrapping_shl(n) } else { 0 };
+                assert_eq!(x.shlx_sat(n), sat, "{} {}", x, n);
+            }
+        }};
+    }
+
+    #[test]
+    fn shlx() {
+        for x in 0..=u8::MAX {
+            check!(x, u8, 31);
+            check!(x, i8, 31);
+        }
+        let mut next = xorshift64(SEED);
+        for _ in 0..256 {
+            let x = next();
+            check!(x, u16, 31);
+            check!(x, i16, 31);
+            check!(x, u32, 31);
+            check!(x, i32, 31);
+            check!(x, u64, 63);
+            check!(x, i64, 63);
+        }
+    }
+}
@@ -0,0 +1,117 @@
+//! shrx
+
+/// Shift logical right without affecting flags
+pub trait Shrx {
+    /// Shift logical right without affecting flags.
+    ///
+    /// Shifts `self` right by `n` bits, shifting in zeros, also for signed
+    /// integers.
+    ///
+    /// The shift count is masked like on x86, on every target: 8 and 16-bit
+    /// integers are shifted as 32-bit integers, so the count is `n % 32` for
+    /// 8, 16 and 32-bit integers, and `n % 64` for 64-bit integers. In
+    /// particular, 8 and 16-bit integers are shifted by the counts in
+    /// `bit_size()..32` instead of masking them to `log2(bit_size())` bits.
+    ///
+    /// # Instructions
+    ///
+    /// - [`SHRX`](http://www.felixcloutier.com/x86/SARX:SHLX:SHRX.html):
+    ///   - Description: Shift logical right without affecting flags.
+    ///   - Architecture: x86.
+    ///   - Instruction set: BMI2.
+    ///   - Registers: 32/64 bit.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bitintr::*;
+    /// assert_eq!(0b1100_0000_u8.shrx(2), 0b0011_0000_u8);
+    /// assert_eq!((-128_i8).shrx(2), 0b0010_0000_i8);
+    /// // The count is masked to 5 bits for 8-bit integers:
+    /// assert_eq!(0b1100_0000_u8.shrx(10), 0);
+    /// assert_eq!(0b1100_0000_u8.shrx(34), 0b0011_0000_u8);
+    /// ```
+    fn shrx(self, n: u32) -> Self;
+
+    /// Saturating shift logical right.
+    ///
+    /// Like [`shrx`](#tymethod.shrx), but shift counts `n >= bit_size()`
+    /// produce 0 instead of being masked. This matches the semantics of
+    /// register-specified shifts on 32-bit ARM.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bitintr::*;
+    /// assert_eq!(0b1100_0000_u8.shrx_sat(2), 0b0011_0000_u8);
+    /// assert_eq!(0b1100_0000_u8.shrx_sat(10), 0);
+    /// assert_eq!((-128_i8).shrx_sat(8), 0);
+    /// ```
+    fn shrx_sat(self, n: u32) -> Self;
+}
+
+macro_rules! impl_shrx {
+    ($id:ident, $uid:ident, $wid:ident) => {
+        #[allow(clippy::use_self)]
+        impl Shrx for $id {
+            #[inline]
+            fn shrx(self, n: u32) -> Self {
+                // `$wid` is the type of the x86 operand, to which `self`
+                // is zero-extended:
+                (self as $uid as $wid).wrapping_shr(n) as Self
+            }
+
+            #[inline]
+            fn shrx_sat(self, n: u32) -> Self {
+                (self as $uid).checked_shr(n).unwrap_or(0) as Self
+            }
+        }
+    };
+}
+
+impl_shrx!(u8, u8, u32);
+impl_shrx!(i8, u8, u32);
+impl_shrx!(u16, u16, u32);
+impl_shrx!(i16, u16, u32);
+impl_shrx!(u32, u32, u32);
+impl_shrx!(i32, u32, u32);
+impl_shrx!(u64, u64, u64);
+impl_shrx!(i64, u64, u64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{xorshift64, SEED};
+
+    macro_rules! check {
+        ($x:expr, $id:ident, $uid:ident, $mask:expr) => {{
+            let x = $x as $id;
+            let ux = x as $uid;
+            for n in 0..=255 {
+                let bits = (crate::mem::size_of::<$id>() * 8) as u32;
+                let expected = ux.checked_shr(n & $mask).unwrap_or(0) as $id;
+                assert_eq!(x.shrx(n), expected, "{} {}", x, n);
+                let sat = if n < bits { ux.wrapping_shr(n) } else { 0 };
+                assert_eq!(x.shrx_sat(n), sat as $id, "{} {}", x, n);
+            }
+        }};
+    }
+
+    #[test]
+    fn shrx() {
+        for x in 0..=u8::MAX {
+            check!(x, u8, u8, 31);
+            check!(x, i8, u8, 31);
+        }
+        let mut next = xorshift64(SEED);
+        for _ in 0..256 {
+            let x = next();
+            check!(x, u16, u16, 31);
+            check!(x, i16, u16, 31);
+            check!(x, u32, u32, 31);
+            check!(x, i32, u32, 31);
+            check!(x, u64, u64, 63);
+            check!(x, i64, u64, 63);
+        }
+    }
+}
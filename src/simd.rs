@@ -0,0 +1,44 @@
+//! Lane-wise implementations for `core::simd` vectors.
+
+use crate::{Bgrp, Pdep, Pext};
+use core::simd::Simd;
+
+macro_rules! impl_lanewise {
+    ($trait_id:ident, $fn_id:ident: $($elem:ident),*) => {
+        $(
+            impl<const N: usize> $trait_id for Simd<$elem, N> {
+                #[inline]
+                fn $fn_id(self, mask: Self) -> Self {
+                    let mut r = self.to_array();
+                    let m = mask.to_array();
+                    for (r, m) in r.iter_mut().zip(m.iter()) {
+                        *r = r.$fn_id(*m);
+                    }
+                    Self::from_array(r)
+                }
+            }
+        )*
+    };
+}
+
+impl_lanewise!(Pdep, pdep: u8, u16, u32, u64, i8, i16, i32, i64);
+impl_lanewise!(Pext, pext: u8, u16, u32, u64, i8, i16, i32, i64);
+impl_lanewise!(Bgrp, bgrp: u8, u16, u32, u64, i8, i16, i32, i64);
+
+#[cfg(test)]
+mod tests {
+    use crate::{Bgrp, Pdep, Pext};
+    use core::simd::Simd;
+
+    #[test]
+    fn lanewise() {
+        let x = [0x1234_u16, 0xBEEF, 0xFFFF, 0x0F0F];
+        let m = [0x00FF_u16, 0xF0F0, 0x8001, 0x1234];
+        let (vx, vm) = (Simd::from_array(x), Simd::from_array(m));
+        for i in 0..4 {
+            assert_eq!(vx.pdep(vm)[i], x[i].pdep(m[i]));
+            assert_eq!(vx.pext(vm)[i], x[i].pext(m[i]));
+            assert_eq!(vx.bgrp(vm)[i], x[i].bgrp(m[i]));
+        }
+    }
+}
@@ -0,0 +1,30 @@
+//! Utilities shared by the unit tests.
+
+/// Seed of the pseudo-random number generators of the tests.
+pub(crate) const SEED: u64 = 0x0123_4567_89AB_CDEF;
+
+/// Returns a xorshift64 pseudo-random number generator seeded with `x`,
+/// which must not be zero.
+pub(crate) fn xorshift64(mut x: u64) -> impl FnMut() -> u64 {
+    debug_assert!(x != 0);
+    move || {
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        x
+    }
+}
+
+/// Parallel bit extract, one bit at a time: gathers the bits of `x`
+/// selected by `mask` into the low bits of the result.
+pub(crate) fn pext(x: u64, mask: u64) -> u64 {
+    let mut r = 0;
+    let mut k = 0;
+    for i in 0..64 {
+        if mask & (1 << i) != 0 {
+            r |= ((x >> i) & 1) << k;
+            k += 1;
+        }
+    }
+    r
+}
@@ -0,0 +1,104 @@
+//! unshfl
+
+use crate::shfl::STAGE_MASKS;
+
+/// Generalized unshuffle
+pub trait Unshfl {
+    /// Generalized unshuffle.
+    ///
+    /// Performs the stages of a perfect outer unshuffle selected by the
+    /// control word `k`, from the least significant stage to the most
+    /// significant one. Only the low `log2(bit_size()) - 1` bits of `k` are
+    /// used.
+    ///
+    /// With all control bits set (`k == bit_size() / 2 - 1`) this gathers the
+    /// even bits of `self` into the low half of the result and the odd bits
+    /// into the high half.
+    ///
+    /// This is the inverse of [`Shfl`](trait.Shfl.html): `x.shfl(k).unshfl(k)
+    /// == x` for all `x` and `k`.
+    ///
+    /// **Keywords**: Generalized unshuffle, perfect unshuffle, deinterleave
+    /// bits, unzip.
+    ///
+    /// # Instructions
+    ///
+    /// - [`UNSHFL`](https://github.com/riscv/riscv-bitmanip):
+    ///   - Description: Generalized unshuffle.
+    ///   - Architecture: RISC-V.
+    ///   - Instruction set: Zbp (draft).
+    ///   - Registers: 32/64 bit.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bitintr::*;
+    /// assert_eq!(0b0101_0101_0101_0101u16.unshfl(7), 0b0000_0000_1111_1111u16);
+    /// assert_eq!(0b1100_1100_1100_1100u16.unshfl(6), 0b1111_1111_0000_0000u16);
+    ///
+    /// let n = 0b1101_0011_1110_1010u16;
+    /// assert_eq!(n.shfl(5).unshfl(5), n);
+    /// ```
+    fn unshfl(self, k: u32) -> Self;
+}
+
+macro_rules! impl_unshfl {
+    ($id:ident, $uid:ident) => {
+        #[allow(clippy::use_self)]
+        impl Unshfl for $id {
+            #[inline]
+            fn unshfl(self, k: u32) -> Self {
+                const BIT_WIDTH: u32 =
+                    (crate::mem::size_of::<$id>() * 8) as u32;
+                let k = k & (BIT_WIDTH / 2 - 1);
+                let mut x = self as $uid;
+                for (i, &m) in STAGE_MASKS.iter().enumerate() {
+                    let s = 1 << i;
+                    if k & s != 0 {
+                        let t = ((x >> s) ^ x) & (m as $uid);
+                        x ^= t ^ (t << s);
+                    }
+                }
+                x as Self
+            }
+        }
+    };
+}
+
+impl_unshfl!(u16, u16);
+impl_unshfl!(i16, u16);
+impl_unshfl!(u32, u32);
+impl_unshfl!(i32, u32);
+impl_unshfl!(u64, u64);
+impl_unshfl!(i64, u64);
+impl_unshfl!(u128, u128);
+impl_unshfl!(i128, u128);
+
+#[cfg(test)]
+mod tests {
+    use crate::{Shfl, Unshfl};
+
+    #[test]
+    fn shfl_unshfl_u16() {
+        for k in 0..8 {
+            (0..=u16::MAX)
+                .map(|x| {
+                    assert_eq!(x, x.shfl(k).unshfl(k));
+                    assert_eq!(x, x.unshfl(k).shfl(k));
+                    let x = x as i16;
+                    assert_eq!(x, x.shfl(k).unshfl(k));
+                    assert_eq!(x, x.unshfl(k).shfl(k));
+                })
+                .count();
+        }
+    }
+
+    #[test]
+    fn shfl_unshfl_u128() {
+        let x = 0x0123_4567_89AB_CDEF_FEDC_BA98_7654_3210_u128;
+        for k in 0..64 {
+            assert_eq!(x, x.shfl(k).unshfl(k));
+            assert_eq!(x, x.unshfl(k).shfl(k));
+        }
+    }
+}
@@ -0,0 +1,114 @@
+//! Unsigned integer words of the `comb`, `ecc` and `gf2` modules.
+//!
+//! The traits are public, so that they can bound the public functions of
+//! these modules, but they live in a private module, so that they can be
+//! neither named nor implemented outside of this crate.
+
+use crate::{Clmul, Lzcnt};
+
+/// Unsigned integers: `u8`, `u16`, `u32`, `u64` and `u128`.
+pub trait Word: Copy + Eq {
+    #[doc(hidden)]
+    const BITS: u32;
+    #[doc(hidden)]
+    const ZERO: Self;
+    #[doc(hidden)]
+    const ONE: Self;
+    #[doc(hidden)]
+    fn xor(self, y: Self) -> Self;
+    #[doc(hidden)]
+    fn shl(self, n: u32) -> Self;
+    #[doc(hidden)]
+    fn bit(self, i: u32) -> bool;
+    #[doc(hidden)]
+    fn lzcnt_(self) -> u32;
+    #[doc(hidden)]
+    fn clmul_(self, y: Self) -> (Self, Self);
+}
+
+/// Unsigned integers of at most 64 bits: `u8`, `u16`, `u32` and `u64`.
+pub trait Narrow: Word {
+    #[doc(hidden)]
+    fn to_u64(self) -> u64;
+    #[doc(hidden)]
+    fn from_u64(x: u64) -> Self;
+}
+
+macro_rules! impl_word {
+    ($id:ident) => {
+        impl Word for $id {
+            const BITS: u32 = (crate::mem::size_of::<$id>() * 8) as u32;
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+            #[inline]
+            fn xor(self, y: Self) -> Self {
+                self ^ y
+            }
+            #[inline]
+            fn shl(self, n: u32) -> Self {
+                self << n
+            }
+            #[inline]
+            fn bit(self, i: u32) -> bool {
+                (self >> i) & 1 != 0
+            }
+            #[inline]
+            fn lzcnt_(self) -> u32 {
+                self.lzcnt() as u32
+            }
+            #[inline]
+            fn clmul_(self, y: Self) -> (Self, Self) {
+                self.clmul(y)
+            }
+        }
+        impl Narrow for $id {
+            #[inline]
+            fn to_u64(self) -> u64 {
+                u64::from(self)
+            }
+            #[inline]
+            fn from_u64(x: u64) -> Self {
+                x as Self
+            }
+        }
+    };
+}
+
+impl_word!(u8);
+impl_word!(u16);
+impl_word!(u32);
+impl_word!(u64);
+
+impl Word for u128 {
+    const BITS: u32 = 128;
+    const ZERO: Self = 0;
+    const ONE: Self = 1;
+    #[inline]
+    fn xor(self, y: Self) -> Self {
+        self ^ y
+    }
+    #[inline]
+    fn shl(self, n: u32) -> Self {
+        self << n
+    }
+    #[inline]
+    fn bit(self, i: u32) -> bool {
+        (self >> i) & 1 != 0
+    }
+    #[inline]
+    fn lzcnt_(self) -> u32 {
+        self.leading_zeros()
+    }
+    #[inline]
+    fn clmul_(self, y: Self) -> (Self, Self) {
+        // Karatsuba: three 64-bit carry-less multiplications.
+        let (a0, a1) = (self as u64, (self >> 64) as u64);
+        let (b0, b1) = (y as u64, (y >> 64) as u64);
+        let join =
+            |(lo, hi): (u64, u64)| (u128::from(hi) << 64) | u128::from(lo);
+        let lo = join(a0.clmul(b0));
+        let hi = join(a1.clmul(b1));
+        let mid = join((a0 ^ a1).clmul(b0 ^ b1)) ^ lo ^ hi;
+        (lo ^ (mid << 64), hi ^ (mid >> 64))
+    }
+}